@@ -0,0 +1,89 @@
+use std::hint::black_box;
+
+use classical_ciphers::{
+    Affine, Amsco, Atbash, Caesar, Cipher, Grille, MagicSquare, Pollux, Polybius, Vigenere, Xor,
+};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+const PARAGRAPH: &str = "It was the best of times, it was the worst of times, it was the age \
+    of wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch \
+    of incredulity, it was the season of Light, it was the season of Darkness. ";
+
+/// Size of the benchmark text in bytes.
+const TEXT_LEN: usize = 10 * 1024;
+
+fn sample_text() -> String {
+    PARAGRAPH.chars().cycle().take(TEXT_LEN).collect()
+}
+
+fn ciphers() -> Vec<(&'static str, Box<dyn Cipher>)> {
+    vec![
+        ("caesar", Box::new(Caesar::new(3))),
+        ("vigenere", Box::new(Vigenere::new("LEMON"))),
+        ("affine", Box::new(Affine::new(5, 8).unwrap())),
+        ("atbash", Box::new(Atbash::new())),
+        ("polybius", Box::new(Polybius::new())),
+        ("magic_square", Box::new(MagicSquare::sun())),
+        ("xor", Box::new(Xor::with_str_key("SECRET"))),
+        ("pollux", Box::new(Pollux::new("..-x.-x.-x").unwrap())),
+        ("amsco", Box::new(Amsco::new("41325").unwrap())),
+        (
+            "grille",
+            Box::new(Grille::new(4, &[(0, 0), (0, 2), (1, 1), (3, 2)]).unwrap()),
+        ),
+    ]
+}
+
+fn encrypt(c: &mut Criterion) {
+    let text = sample_text();
+    let mut group = c.benchmark_group("encrypt");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    for (name, cipher) in ciphers() {
+        group.bench_function(name, |b| b.iter(|| cipher.encrypt(black_box(&text))));
+    }
+    group.finish();
+}
+
+fn round_trip(c: &mut Criterion) {
+    let text = sample_text();
+    let mut group = c.benchmark_group("round_trip");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    for (name, cipher) in ciphers() {
+        group.bench_function(name, |b| {
+            b.iter(|| cipher.decrypt(&cipher.encrypt(black_box(&text))))
+        });
+    }
+    group.finish();
+}
+
+/// Compares growing a `String` through `collect` with filling one sized
+/// up front, on the same per-character shift the substitution ciphers use.
+fn string_building(c: &mut Criterion) {
+    let text = sample_text();
+    let shift = |c: char| {
+        if c.is_ascii_uppercase() {
+            (b'A' + (c as u8 - b'A' + 3) % 26) as char
+        } else {
+            c
+        }
+    };
+    let mut group = c.benchmark_group("string_building");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("collect", |b| {
+        b.iter(|| black_box(&text).chars().map(shift).collect::<String>())
+    });
+    group.bench_function("with_capacity", |b| {
+        b.iter(|| {
+            let input = black_box(&text);
+            let mut output = String::with_capacity(input.len());
+            for c in input.chars() {
+                output.push(shift(c));
+            }
+            output
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, encrypt, round_trip, string_building);
+criterion_main!(benches);
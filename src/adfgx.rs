@@ -0,0 +1,202 @@
+use crate::{Cipher, Polybius};
+
+/// Row and column labels, in place of the digits 1-5.
+const LABELS: [char; 5] = ['A', 'D', 'F', 'G', 'X'];
+
+/// Letters per group in the ciphertext by default.
+const GROUP_LEN: usize = 5;
+
+/// ADFGX cipher - the German WWI field cipher.
+///
+/// Each letter is looked up in a keyed 5x5 Polybius square (I and J share
+/// a cell) and replaced by its row and column labels from A, D, F, G and
+/// X. The label stream is then written row by row under the transposition
+/// key and read out column by column in the alphabetical order of the key
+/// (ties left to right). The ciphertext is printed in uppercase groups of
+/// five; see [`Adfgx::with_group_len`] and [`Adfgx::lowercase`].
+///
+/// Only letters are encrypted; everything else is dropped, and decryption
+/// returns uppercase letters with J as I.
+#[derive(Debug, Clone)]
+pub struct Adfgx {
+    square: Polybius,
+    order: Vec<usize>,
+    group_len: usize,
+    lowercase: bool,
+}
+
+impl Adfgx {
+    /// Creates an ADFGX cipher from a square keyword and a transposition key.
+    ///
+    /// Returns `None` if the transposition key has no letters or digits.
+    pub fn new(square_key: &str, transposition_key: &str) -> Option<Self> {
+        let key: Vec<char> = transposition_key
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_uppercase)
+            .collect();
+        if key.is_empty() {
+            return None;
+        }
+
+        let mut order: Vec<usize> = (0..key.len()).collect();
+        order.sort_by_key(|&i| key[i]);
+        Some(Self {
+            square: Polybius::with_key(square_key),
+            order,
+            group_len: GROUP_LEN,
+            lowercase: false,
+        })
+    }
+
+    /// Groups the ciphertext into runs of `len` letters; 0 disables grouping.
+    pub fn with_group_len(mut self, len: usize) -> Self {
+        self.group_len = len;
+        self
+    }
+
+    /// Writes the ciphertext labels in lowercase. Decryption accepts either.
+    pub fn lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+
+    /// Reads the columns of `stream` out in key order.
+    fn transpose(&self, stream: &[char]) -> Vec<char> {
+        let columns = self.order.len();
+        self.order
+            .iter()
+            .flat_map(|&column| stream.iter().skip(column).step_by(columns))
+            .copied()
+            .collect()
+    }
+
+    /// Refills the columns in key order and reads the grid row by row.
+    fn untranspose(&self, stream: &[char]) -> Vec<char> {
+        let columns = self.order.len();
+        let (rows, long_columns) = (stream.len() / columns, stream.len() % columns);
+        let mut grid = vec![' '; stream.len()];
+        let mut read = stream.iter();
+
+        for &column in &self.order {
+            let height = rows + usize::from(column < long_columns);
+            for row in 0..height {
+                grid[row * columns + column] = *read.next().unwrap_or(&' ');
+            }
+        }
+        grid
+    }
+}
+
+impl Cipher for Adfgx {
+    fn encrypt(&self, input: &str) -> String {
+        let letters: String = input.chars().filter(char::is_ascii_alphabetic).collect();
+        let labels: Vec<char> = self
+            .square
+            .encrypt(&letters)
+            .chars()
+            .filter_map(|d| {
+                LABELS
+                    .get(d.to_digit(10)?.checked_sub(1)? as usize)
+                    .copied()
+            })
+            .collect();
+
+        let mut output = self.transpose(&labels);
+        if self.lowercase {
+            output.iter_mut().for_each(char::make_ascii_lowercase);
+        }
+        if self.group_len == 0 {
+            return output.into_iter().collect();
+        }
+        output
+            .chunks(self.group_len)
+            .map(|group| group.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        let labels: Vec<char> = input
+            .chars()
+            .map(|c| c.to_ascii_uppercase())
+            .filter(|c| LABELS.contains(c))
+            .collect();
+        let digits: String = self
+            .untranspose(&labels)
+            .iter()
+            .filter_map(|c| LABELS.iter().position(|l| l == c))
+            .filter_map(|i| char::from_digit(i as u32 + 1, 10))
+            .collect();
+        self.square.decrypt(&digits)
+    }
+
+    /// Two labels per letter, plus a space between groups.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        let labels = 2 * input.chars().filter(char::is_ascii_alphabetic).count();
+        match self.group_len {
+            0 => labels,
+            len => labels + labels.saturating_sub(1) / len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_attack_at_dawn() {
+        let cipher = Adfgx::new("BATTLESHIP", "CARGO").unwrap();
+        let encrypted = cipher.encrypt("ATTACKATDAWN");
+        assert!(encrypted.chars().all(|c| "ADFGX".contains(c) || c == ' '));
+        assert_eq!(encrypted.replace(' ', "").len(), 24);
+        let groups: Vec<&str> = encrypted.split(' ').collect();
+        assert_eq!(groups.len(), 5);
+        assert!(groups[..4].iter().all(|group| group.len() == GROUP_LEN));
+        assert_eq!(cipher.decrypt(&encrypted), "ATTACKATDAWN");
+    }
+
+    #[test]
+    fn substitution_then_transposition() {
+        // Standard square: A = 11 -> AA, T = 44 -> GG, B = 12 -> AD.
+        // Under key "BA" the second column is read first.
+        let cipher = Adfgx::new("", "BA").unwrap();
+        assert_eq!(cipher.encrypt("ATB"), "AGDAG A");
+        assert_eq!(cipher.decrypt("AGDAG A"), "ATB");
+    }
+
+    #[test]
+    fn grouping_and_case() {
+        let cipher = Adfgx::new("", "BA").unwrap().with_group_len(2).lowercase();
+        assert_eq!(cipher.encrypt("ATB"), "ag da ga");
+        assert_eq!(cipher.decrypt("ag da ga"), "ATB");
+
+        let ungrouped = Adfgx::new("", "BA").unwrap().with_group_len(0);
+        assert_eq!(ungrouped.encrypt("ATB"), "AGDAGA");
+    }
+
+    #[test]
+    fn uneven_columns_and_j() {
+        let cipher = Adfgx::new("KEYWORD", "GERMAN").unwrap();
+        let encrypted = cipher.encrypt("Jump over, then regroup!");
+        assert_eq!(cipher.decrypt(&encrypted), "IUMPOVERTHENREGROUP");
+    }
+
+    #[test]
+    fn estimated_output_len_matches_output() {
+        let cipher = Adfgx::new("PRIVACY", "GERMAN").unwrap();
+        for (plaintext, expected) in [("ATTACKATDAWN", 28), ("Attack at dawn!", 28), ("", 0)] {
+            assert_eq!(cipher.encrypt(plaintext).len(), expected);
+            assert_eq!(cipher.estimated_output_len(plaintext), expected);
+        }
+        let ungrouped = cipher.with_group_len(0);
+        assert_eq!(ungrouped.estimated_output_len("ATTACKATDAWN"), 24);
+    }
+
+    #[test]
+    fn rejects_empty_transposition_key() {
+        assert!(Adfgx::new("KEY", "").is_none());
+        assert!(Adfgx::new("KEY", "--").is_none());
+    }
+}
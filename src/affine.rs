@@ -1,4 +1,7 @@
-use crate::Cipher;
+use crate::alphabet::AlphabetRef;
+use crate::analysis;
+use crate::layout;
+use crate::{Alphabet, Cipher, InputClasses, Monoalphabetic};
 
 /// Affine cipher implementation.
 ///
@@ -9,23 +12,88 @@ use crate::Cipher;
 /// The key consists of two values:
 /// - `a`: must be coprime with 26 (valid values: 1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25)
 /// - `b`: any value from 0-25
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Affine {
     a: i32,
     a_inv: i32,
     b: i32,
+    alphabet: AlphabetRef,
+}
+
+/// A validated key for the A-Z Affine cipher.
+///
+/// Naming the two parts avoids mixing up `a` and `b` at call sites.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AffineKey {
+    a: i32,
+    b: i32,
+}
+
+impl AffineKey {
+    /// Creates a key, reducing both parts modulo 26.
+    ///
+    /// Returns `None` if `a` is not coprime with 26.
+    pub fn new(a: i32, b: i32) -> Option<Self> {
+        mod_inverse(a, 26)?;
+        Some(Self {
+            a: a.rem_euclid(26),
+            b: b.rem_euclid(26),
+        })
+    }
+
+    /// Picks a key uniformly from the 312 valid keys.
+    #[cfg(feature = "rand")]
+    pub fn random() -> Self {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        Self {
+            a: VALID_A[rng.gen_range(0..VALID_A.len())],
+            b: rng.gen_range(0..26),
+        }
+    }
+
+    /// The multiplier.
+    pub fn a(&self) -> i32 {
+        self.a
+    }
+
+    /// The shift.
+    pub fn b(&self) -> i32 {
+        self.b
+    }
 }
 
 impl Affine {
+    /// Creates an A-Z Affine cipher from a validated key.
+    pub fn from_key(key: AffineKey) -> Self {
+        Self {
+            a: key.a,
+            // AffineKey::new only accepts multipliers that have an inverse.
+            a_inv: mod_inverse(key.a, 26).unwrap_or(1),
+            b: key.b,
+            alphabet: AlphabetRef::Latin,
+        }
+    }
+
     /// Creates a new Affine cipher with the given keys.
     ///
     /// Returns `None` if `a` is not coprime with 26.
     pub fn new(a: i32, b: i32) -> Option<Self> {
-        let a_inv = mod_inverse(a, 26)?;
+        Self::with_alphabet(a, b, Alphabet::latin())
+    }
+
+    /// Creates an Affine cipher over `alphabet`, working modulo its length.
+    ///
+    /// Returns `None` if `a` is not coprime with the alphabet length.
+    pub fn with_alphabet(a: i32, b: i32, alphabet: Alphabet) -> Option<Self> {
+        let m = alphabet.len() as i32;
+        let a_inv = mod_inverse(a, m)?;
         Some(Self {
-            a: a.rem_euclid(26),
+            a: a.rem_euclid(m),
             a_inv,
-            b: b.rem_euclid(26),
+            b: b.rem_euclid(m),
+            alphabet: alphabet.into(),
         })
     }
 
@@ -35,6 +103,7 @@ impl Affine {
             a: 1,
             a_inv: 1,
             b: shift.rem_euclid(26),
+            alphabet: AlphabetRef::Latin,
         }
     }
 
@@ -43,40 +112,92 @@ impl Affine {
         Self::caesar(13)
     }
 
-    fn transform_char(&self, c: char, encrypt: bool) -> char {
-        if !c.is_ascii_alphabetic() {
-            return c;
-        }
+    /// Returns the 12 values of `a` that are coprime with 26.
+    pub fn valid_a_values() -> [i32; 12] {
+        VALID_A
+    }
+
+    /// Decrypts `ciphertext`, then restores letter case from `case_pattern`
+    /// (true = uppercase), one entry per letter.
+    pub fn decrypt_with_case(&self, ciphertext: &str, case_pattern: &[bool]) -> String {
+        layout::apply_case(&self.decrypt(ciphertext), case_pattern)
+    }
+
+    /// Yields all 312 valid `(a, b)` key pairs for the A-Z alphabet.
+    pub fn all_valid_keys() -> impl Iterator<Item = (i32, i32)> {
+        VALID_A
+            .into_iter()
+            .flat_map(|a| (0..26).map(move |b| (a, b)))
+    }
 
-        let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
-        let x = (c as u8 - base) as i32;
+    /// Tries all 312 valid keys and returns the best `(a, b)` with its
+    /// plaintext.
+    ///
+    /// Returns `None` unless the best plaintext's [`analysis::english_score`]
+    /// is at least `threshold`.
+    pub fn crack_auto(ciphertext: &str, threshold: f64) -> Option<((i32, i32), String)> {
+        Self::all_valid_keys()
+            .filter_map(|(a, b)| Self::new(a, b))
+            .map(|cipher| {
+                let plaintext = cipher.decrypt(ciphertext);
+                let score = analysis::english_score(&plaintext);
+                ((cipher.a, cipher.b), plaintext, score)
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .filter(|&(_, _, score)| score >= threshold)
+            .map(|(key, plaintext, _)| (key, plaintext))
+    }
 
-        let result = if encrypt {
-            (self.a * x + self.b).rem_euclid(26)
-        } else {
-            (self.a_inv * (x - self.b)).rem_euclid(26)
-        };
+    fn transform(&self, input: &str, encrypt: bool) -> String {
+        let mut output = String::with_capacity(input.len());
+        for c in input.chars() {
+            output.push(self.transform_char(c, encrypt));
+        }
+        output
+    }
 
-        (base + result as u8) as char
+    fn transform_char(&self, c: char, encrypt: bool) -> char {
+        let (a, a_inv, b) = (self.a as i64, self.a_inv as i64, self.b as i64);
+        self.alphabet
+            .substitute(c, |x| {
+                let x = x as i64;
+                if encrypt {
+                    a * x + b
+                } else {
+                    a_inv * (x - b)
+                }
+            })
+            .unwrap_or(c)
     }
 }
 
 impl Cipher for Affine {
     fn encrypt(&self, input: &str) -> String {
-        input
-            .chars()
-            .map(|c| self.transform_char(c, true))
-            .collect()
+        self.transform(input, true)
     }
 
     fn decrypt(&self, input: &str) -> String {
-        input
-            .chars()
-            .map(|c| self.transform_char(c, false))
-            .collect()
+        self.transform(input, false)
+    }
+
+    fn preserves_length(&self) -> bool {
+        true
+    }
+
+    fn keyspace(&self) -> Option<u128> {
+        let m = self.alphabet.len() as i32;
+        let multipliers = (1..m).filter(|&a| mod_inverse(a, m).is_some()).count();
+        Some((multipliers * m as usize) as u128)
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        self.alphabet.classes()
     }
 }
 
+/// Residues modulo 26 that have a multiplicative inverse.
+const VALID_A: [i32; 12] = [1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25];
+
 /// Computes the modular multiplicative inverse of `a` modulo `m`.
 /// Returns `None` if no inverse exists (i.e., gcd(a, m) != 1).
 fn mod_inverse(a: i32, m: i32) -> Option<i32> {
@@ -97,9 +218,44 @@ fn mod_inverse(a: i32, m: i32) -> Option<i32> {
     Some(old_s.rem_euclid(m))
 }
 
+impl Monoalphabetic for Affine {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutil::{random_plaintext, ENGLISH};
+
+    #[test]
+    fn from_key_matches_new() {
+        let key = AffineKey::new(5, 8).unwrap();
+        assert_eq!((key.a(), key.b()), (5, 8));
+        assert_eq!(
+            Affine::from_key(key).encrypt("AFFINE CIPHER"),
+            Affine::new(5, 8).unwrap().encrypt("AFFINE CIPHER")
+        );
+        assert_eq!(AffineKey::new(31, -1), AffineKey::new(5, 25));
+        assert!(AffineKey::new(13, 1).is_none());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_keys_are_valid() {
+        for _ in 0..100 {
+            let key = AffineKey::random();
+            assert!(Affine::valid_a_values().contains(&key.a()));
+            assert!((0..26).contains(&key.b()));
+            let cipher = Affine::from_key(key);
+            assert_eq!(cipher.decrypt(&cipher.encrypt("ROUND TRIP")), "ROUND TRIP");
+        }
+    }
+
+    #[test]
+    fn decrypt_with_case_mask() {
+        let cipher = Affine::new(5, 8).unwrap();
+        let ciphertext = cipher.encrypt("Hello").to_uppercase();
+        let mask = [true, false, false, false, false];
+        assert_eq!(cipher.decrypt_with_case(&ciphertext, &mask), "Hello");
+    }
 
     #[test]
     fn encrypt_basic() {
@@ -153,6 +309,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn all_valid_keys_are_distinct_and_coprime() {
+        let keys: std::collections::HashSet<(i32, i32)> = Affine::all_valid_keys().collect();
+        assert_eq!(keys.len(), 312);
+        assert_eq!(Affine::all_valid_keys().count(), 312);
+        for (a, b) in keys {
+            assert!(mod_inverse(a, 26).is_some(), "a={} is not coprime", a);
+            assert!((0..26).contains(&b));
+        }
+        assert!(Affine::valid_a_values()
+            .iter()
+            .all(|&a| Affine::new(a, 0).is_some()));
+    }
+
+    #[test]
+    fn keyspace() {
+        assert_eq!(Affine::new(5, 8).unwrap().keyspace(), Some(312));
+        let alphabet = Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ ").unwrap();
+        let cipher = Affine::with_alphabet(2, 1, alphabet).unwrap();
+        assert_eq!(cipher.keyspace(), Some(18 * 27));
+    }
+
+    #[test]
+    fn custom_alphabet_modulus() {
+        let alphabet = Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZ ").unwrap();
+        assert!(Affine::new(2, 1).is_none());
+        let cipher = Affine::with_alphabet(2, 1, alphabet).unwrap();
+        let encrypted = cipher.encrypt("MEET AT DAWN");
+        assert_eq!(encrypted, "ZJJM BM HBSA");
+        assert_eq!(cipher.decrypt(&encrypted), "MEET AT DAWN");
+    }
+
     #[test]
     fn caesar_special_case() {
         let affine = Affine::caesar(3);
@@ -181,4 +369,20 @@ mod tests {
         assert_eq!(mod_inverse(7, 26), Some(15));
         assert_eq!(mod_inverse(2, 26), None);
     }
+
+    #[test]
+    fn crack_auto_accepts_english() {
+        let ciphertext = Affine::new(7, 11).unwrap().encrypt(ENGLISH);
+        assert_eq!(
+            Affine::crack_auto(&ciphertext, 0.75),
+            Some(((7, 11), ENGLISH.to_string()))
+        );
+    }
+
+    #[test]
+    fn crack_auto_rejects_noise() {
+        let letters: Vec<char> = ('A'..='Z').collect();
+        let noise = random_plaintext(0x1234_5678, 400, &letters);
+        assert_eq!(Affine::crack_auto(&noise, 0.75), None);
+    }
 }
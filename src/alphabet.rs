@@ -0,0 +1,332 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::InputClasses;
+
+/// An ordered set of symbols that substitution ciphers shift within.
+///
+/// Positions are zero-based. With case folding enabled, a character that
+/// is not in the alphabet but whose other case is still matches, and
+/// results are returned in the case of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    chars: Vec<char>,
+    fold_case: bool,
+}
+
+impl Alphabet {
+    /// Creates a case-sensitive alphabet from the characters of `chars`.
+    ///
+    /// Returns `None` if `chars` is empty or contains a duplicate.
+    pub fn new(chars: &str) -> Option<Self> {
+        let chars: Vec<char> = chars.chars().collect();
+        if chars.is_empty()
+            || chars
+                .iter()
+                .enumerate()
+                .any(|(i, c)| chars[..i].contains(c))
+        {
+            return None;
+        }
+        Some(Self {
+            chars,
+            fold_case: false,
+        })
+    }
+
+    /// The 26 letters A-Z, matching either case.
+    pub fn latin() -> Self {
+        Self::from_ascii("ABCDEFGHIJKLMNOPQRSTUVWXYZ")
+    }
+
+    /// A-Z followed by 0-9, matching letters in either case.
+    pub fn latin_alphanumeric() -> Self {
+        Self::from_ascii("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789")
+    }
+
+    /// The five vowels A, E, I, O, U, matching either case.
+    pub fn vowels() -> Self {
+        Self::from_ascii("AEIOU")
+    }
+
+    /// The 21 consonants B-Z without vowels, matching either case.
+    pub fn consonants() -> Self {
+        Self::from_ascii("BCDFGHJKLMNPQRSTVWXYZ")
+    }
+
+    /// A-Z followed by a-z as 52 distinct symbols.
+    pub fn latin_mixed_case() -> Self {
+        Self {
+            chars: ('A'..='Z').chain('a'..='z').collect(),
+            fold_case: false,
+        }
+    }
+
+    fn from_ascii(chars: &str) -> Self {
+        Self {
+            chars: chars.chars().collect(),
+            fold_case: true,
+        }
+    }
+
+    /// Matches characters in either case.
+    pub fn with_case_folding(mut self) -> Self {
+        self.fold_case = true;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Always false: an alphabet has at least one symbol.
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Returns the position of `c`, if it belongs to the alphabet.
+    pub fn index_of(&self, c: char) -> Option<usize> {
+        self.position(c)
+            .or_else(|| self.folded(c).and_then(|v| self.position(v)))
+    }
+
+    /// Returns the symbol at `index`, if it is in range.
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        self.chars.get(index).copied()
+    }
+
+    /// Shifts `c` by `amount` positions, wrapping around the alphabet.
+    ///
+    /// Returns `None` if `c` is not in the alphabet.
+    pub fn shift(&self, c: char, amount: i64) -> Option<char> {
+        self.substitute(c, |i| i as i64 + amount)
+    }
+
+    /// Replaces `c` with the symbol at `f(index)`, reduced modulo the length.
+    ///
+    /// Returns `None` if `c` is not in the alphabet.
+    pub fn substitute(&self, c: char, f: impl FnOnce(usize) -> i64) -> Option<char> {
+        let (index, folded) = match self.position(c) {
+            Some(index) => (index, false),
+            None => (self.position(self.folded(c)?)?, true),
+        };
+        let result = self.chars[f(index).rem_euclid(self.len() as i64) as usize];
+        Some(if folded {
+            other_case(result).unwrap_or(result)
+        } else {
+            result
+        })
+    }
+
+    /// The classes of the symbols in the alphabet.
+    pub(crate) fn classes(&self) -> InputClasses {
+        self.chars.iter().copied().collect()
+    }
+
+    fn position(&self, c: char) -> Option<usize> {
+        self.chars.iter().position(|&a| a == c)
+    }
+
+    fn folded(&self, c: char) -> Option<char> {
+        if self.fold_case {
+            other_case(c)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::latin()
+    }
+}
+
+/// A cheaply cloned handle to an alphabet.
+///
+/// A-Z is handled by arithmetic on ASCII; any other alphabet is shared
+/// behind an `Arc`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) enum AlphabetRef {
+    #[default]
+    Latin,
+    Custom(Arc<Alphabet>),
+}
+
+impl AlphabetRef {
+    /// The alphabet when it is not plain A-Z.
+    pub(crate) fn custom(&self) -> Option<&Alphabet> {
+        match self {
+            Self::Latin => None,
+            Self::Custom(alphabet) => Some(alphabet),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::Latin => 26,
+            Self::Custom(alphabet) => alphabet.len(),
+        }
+    }
+
+    pub(crate) fn char_at(&self, index: usize) -> Option<char> {
+        match self {
+            Self::Latin => (index < 26).then(|| (b'A' + index as u8) as char),
+            Self::Custom(alphabet) => alphabet.char_at(index),
+        }
+    }
+
+    pub(crate) fn shift(&self, c: char, amount: i64) -> Option<char> {
+        self.substitute(c, |i| i as i64 + amount)
+    }
+
+    pub(crate) fn substitute(&self, c: char, f: impl FnOnce(usize) -> i64) -> Option<char> {
+        match self {
+            Self::Latin => {
+                if !c.is_ascii_alphabetic() {
+                    return None;
+                }
+                let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
+                let index = f((c as u8 - base) as usize).rem_euclid(26) as u8;
+                Some((base + index) as char)
+            }
+            Self::Custom(alphabet) => alphabet.substitute(c, f),
+        }
+    }
+
+    pub(crate) fn classes(&self) -> InputClasses {
+        match self {
+            Self::Latin => InputClasses::LETTERS,
+            Self::Custom(alphabet) => alphabet.classes(),
+        }
+    }
+}
+
+impl From<Alphabet> for AlphabetRef {
+    fn from(alphabet: Alphabet) -> Self {
+        if alphabet == Alphabet::latin() {
+            Self::Latin
+        } else {
+            Self::Custom(Arc::new(alphabet))
+        }
+    }
+}
+
+/// Writes the symbols in order, e.g. "AEIOU".
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chars.iter().try_for_each(|c| write!(f, "{c}"))
+    }
+}
+
+/// Returns the other-case form of `c` when the mapping is one-to-one.
+///
+/// Characters such as 'ß' or the dotless 'ı', whose case mapping does not
+/// round-trip, have no other case here.
+fn other_case(c: char) -> Option<char> {
+    let swap = |c: char| {
+        if c.is_lowercase() {
+            single(c.to_uppercase())
+        } else if c.is_uppercase() {
+            single(c.to_lowercase())
+        } else {
+            None
+        }
+    };
+    swap(c).filter(|&v| swap(v) == Some(c))
+}
+
+fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latin_lookup() {
+        let alphabet = Alphabet::latin();
+        assert_eq!(alphabet.len(), 26);
+        assert_eq!(alphabet.index_of('A'), Some(0));
+        assert_eq!(alphabet.index_of('z'), Some(25));
+        assert_eq!(alphabet.index_of('1'), None);
+        assert_eq!(alphabet.char_at(2), Some('C'));
+        assert_eq!(alphabet.char_at(26), None);
+    }
+
+    #[test]
+    fn shift_wraps_and_keeps_case() {
+        let alphabet = Alphabet::latin();
+        assert_eq!(alphabet.shift('Y', 3), Some('B'));
+        assert_eq!(alphabet.shift('y', 3), Some('b'));
+        assert_eq!(alphabet.shift('a', -1), Some('z'));
+        assert_eq!(alphabet.shift('!', 3), None);
+    }
+
+    #[test]
+    fn substitute_reduces_modulo_length() {
+        let alphabet = Alphabet::latin();
+        assert_eq!(alphabet.substitute('B', |i| 25 - i as i64), Some('Y'));
+        assert_eq!(alphabet.substitute('c', |i| 7 * i as i64 + 100), Some('k'));
+    }
+
+    #[test]
+    fn custom_alphabet_is_case_sensitive() {
+        let alphabet = Alphabet::new("абвгд").unwrap();
+        assert_eq!(alphabet.shift('д', 1), Some('а'));
+        assert_eq!(alphabet.shift('Д', 1), None);
+
+        let folding = alphabet.with_case_folding();
+        assert_eq!(folding.shift('Д', 1), Some('А'));
+    }
+
+    #[test]
+    fn alphanumeric_digits() {
+        let alphabet = Alphabet::latin_alphanumeric();
+        assert_eq!(alphabet.len(), 36);
+        assert_eq!(alphabet.shift('9', 1), Some('A'));
+        assert_eq!(alphabet.shift('z', 1), Some('0'));
+    }
+
+    #[test]
+    fn mixed_case_keeps_cases_apart() {
+        let alphabet = Alphabet::latin_mixed_case();
+        assert_eq!(alphabet.len(), 52);
+        assert_eq!(alphabet.index_of('a'), Some(26));
+        assert_eq!(alphabet.shift('Z', 1), Some('a'));
+        assert_eq!(alphabet.shift('z', 1), Some('A'));
+    }
+
+    #[test]
+    fn alphabet_ref_matches_alphabet() {
+        let latin = Alphabet::latin();
+        let fast = AlphabetRef::from(latin.clone());
+        assert_eq!(fast, AlphabetRef::Latin);
+        assert_eq!(fast.char_at(25), Some('Z'));
+        assert_eq!(fast.char_at(26), None);
+        for c in ['A', 'z', 'm', '!', 'é', 'ſ'] {
+            assert_eq!(fast.shift(c, 29), latin.shift(c, 29));
+        }
+
+        let vowels = AlphabetRef::from(Alphabet::vowels());
+        assert_eq!(vowels.len(), 5);
+        assert_eq!(vowels.shift('u', 1), Some('a'));
+    }
+
+    #[test]
+    fn folding_ignores_lossy_case_mappings() {
+        let alphabet = Alphabet::latin();
+        assert_eq!(alphabet.index_of('ı'), None);
+        assert_eq!(alphabet.index_of('ſ'), None);
+        assert_eq!(alphabet.index_of('ß'), None);
+    }
+
+    #[test]
+    fn rejects_empty_and_duplicates() {
+        assert!(Alphabet::new("").is_none());
+        assert!(Alphabet::new("ABCA").is_none());
+        assert_eq!(Alphabet::default(), Alphabet::latin());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::layout;
+use crate::Cipher;
+
+/// AMSCO cipher - columnar transposition with alternating chunk sizes.
+///
+/// The letters are written row by row under the key, alternating between
+/// chunks of one and two letters, and the columns are then read out in the
+/// alphabetical order of their key characters (ties left to right). By
+/// default the first chunk is a single letter; use
+/// [`Amsco::starting_with_pair`] to begin with a two-letter chunk instead.
+///
+/// Only letters are transposed. Spaces, digits and punctuation stay at
+/// their original positions, so word lengths are visible in the output.
+#[derive(Debug, Clone)]
+pub struct Amsco {
+    order: Vec<usize>,
+    first_chunk: usize,
+}
+
+impl Amsco {
+    /// Creates an AMSCO cipher from a keyword or a digit key such as "41325".
+    ///
+    /// Returns `None` if the key has no letters or digits.
+    pub fn new(key: &str) -> Option<Self> {
+        let key: Vec<char> = key
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_uppercase)
+            .collect();
+        if key.is_empty() {
+            return None;
+        }
+
+        let mut order: Vec<usize> = (0..key.len()).collect();
+        order.sort_by_key(|&i| key[i]);
+        Some(Self {
+            order,
+            first_chunk: 1,
+        })
+    }
+
+    /// Starts the fill with a two-letter chunk rather than a single letter.
+    pub fn starting_with_pair(mut self) -> Self {
+        self.first_chunk = 2;
+        self
+    }
+
+    /// Draws the fill grid for `text`'s letters, for teaching.
+    ///
+    /// The first line gives each column's place in the read order, then
+    /// each row shows its one- and two-letter chunks. Cells left empty by
+    /// a short last row are shown as '.'.
+    ///
+    /// ```text
+    /// 3  1  2
+    /// H  EL L
+    /// O  .  .
+    /// ```
+    pub fn render_grid(&self, text: &str) -> String {
+        let (letters, _) = layout::strip_non_letters(text);
+        let letters: Vec<char> = letters.chars().collect();
+        let columns = self.order.len();
+
+        let mut cells: Vec<String> = Vec::new();
+        let mut offset = 0;
+        for size in self.chunk_sizes(letters.len()) {
+            cells.push(letters[offset..offset + size].iter().collect());
+            offset += size;
+        }
+        cells.resize(cells.len().div_ceil(columns) * columns, ".".to_string());
+
+        let mut ranks = vec![0; columns];
+        for (rank, &column) in self.order.iter().enumerate() {
+            ranks[column] = rank + 1;
+        }
+        let header: Vec<String> = ranks.iter().map(usize::to_string).collect();
+        let width = header.iter().map(String::len).max().unwrap_or(0).max(2);
+
+        std::iter::once(&header[..])
+            .chain(cells.chunks(columns))
+            .map(|row| render_row(row, width))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Chunk lengths for `len` letters, in fill order.
+    fn chunk_sizes(&self, len: usize) -> Vec<usize> {
+        let mut sizes = Vec::new();
+        let mut remaining = len;
+        let mut size = self.first_chunk;
+        while remaining > 0 {
+            let take = size.min(remaining);
+            sizes.push(take);
+            remaining -= take;
+            size = 3 - size;
+        }
+        sizes
+    }
+
+    /// Chunk indices in the order the columns are read out.
+    fn read_order(&self, chunks: usize) -> Vec<usize> {
+        let columns = self.order.len();
+        self.order
+            .iter()
+            .flat_map(|&column| (column..chunks).step_by(columns))
+            .collect()
+    }
+
+    fn transpose(&self, letters: &str) -> String {
+        let letters: Vec<char> = letters.chars().collect();
+        let sizes = self.chunk_sizes(letters.len());
+        let starts: Vec<usize> = sizes
+            .iter()
+            .scan(0, |offset, &size| {
+                let start = *offset;
+                *offset += size;
+                Some(start)
+            })
+            .collect();
+
+        self.read_order(sizes.len())
+            .into_iter()
+            .flat_map(|k| &letters[starts[k]..starts[k] + sizes[k]])
+            .collect()
+    }
+
+    fn untranspose(&self, letters: &str) -> String {
+        let mut letters = letters.chars();
+        let sizes = self.chunk_sizes(letters.clone().count());
+        let mut chunks = vec![String::new(); sizes.len()];
+
+        for k in self.read_order(sizes.len()) {
+            chunks[k] = letters.by_ref().take(sizes[k]).collect();
+        }
+
+        chunks.concat()
+    }
+}
+
+fn render_row(cells: &[String], width: usize) -> String {
+    cells
+        .iter()
+        .map(|cell| format!("{:<width$}", cell, width = width))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end()
+        .to_string()
+}
+
+impl Cipher for Amsco {
+    fn encrypt(&self, input: &str) -> String {
+        let (letters, fixed) = layout::strip_non_letters(input);
+        layout::restore(&self.transpose(&letters), &fixed)
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        let (letters, fixed) = layout::strip_non_letters(input);
+        layout::restore(&self.untranspose(&letters), &fixed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_single_first() {
+        // A|BC|D / EF|G|HI / J under C A B, read as columns A, B, C.
+        let cipher = Amsco::new("CAB").unwrap();
+        assert_eq!(cipher.encrypt("ABCDEFGHIJ"), "BCGDHIAEFJ");
+        assert_eq!(cipher.decrypt("BCGDHIAEFJ"), "ABCDEFGHIJ");
+    }
+
+    #[test]
+    fn encrypt_pair_first() {
+        // AB|C|DE / F|GH|I / J under C A B.
+        let cipher = Amsco::new("CAB").unwrap().starting_with_pair();
+        assert_eq!(cipher.encrypt("ABCDEFGHIJ"), "CGHDEIABFJ");
+        assert_eq!(cipher.decrypt("CGHDEIABFJ"), "ABCDEFGHIJ");
+    }
+
+    #[test]
+    fn textbook_round_trip() {
+        let plaintext = "INCOMPLETE COLUMNAR WITH ALTERNATING SINGLE LETTERS AND DIGRAPHS";
+        for cipher in [
+            Amsco::new("41325").unwrap(),
+            Amsco::new("41325").unwrap().starting_with_pair(),
+        ] {
+            let encrypted = cipher.encrypt(plaintext);
+            assert_ne!(encrypted, plaintext);
+            assert_eq!(cipher.decrypt(&encrypted), plaintext);
+        }
+    }
+
+    #[test]
+    fn keyword_with_repeated_letters() {
+        let cipher = Amsco::new("Attack").unwrap();
+        let encrypted = cipher.encrypt("Meet me by the old oak tree");
+        assert_eq!(cipher.decrypt(&encrypted), "Meet me by the old oak tree");
+    }
+
+    #[test]
+    fn non_letters_stay_in_place() {
+        let cipher = Amsco::new("CAB").unwrap();
+        assert_eq!(cipher.encrypt("ABCDE, FGHIJ!"), "BCGDH, IAEFJ!");
+    }
+
+    #[test]
+    fn render_grid_shows_chunks() {
+        let cipher = Amsco::new("CAB").unwrap();
+        assert_eq!(cipher.render_grid("HELLO"), "3  1  2\nH  EL L\nO  .  .");
+        assert_eq!(
+            Amsco::new("CAB")
+                .unwrap()
+                .starting_with_pair()
+                .render_grid("Hel, lo"),
+            "3  1  2\nHe l  lo"
+        );
+    }
+
+    #[test]
+    fn rejects_empty_key() {
+        assert!(Amsco::new("").is_none());
+        assert!(Amsco::new(" -!").is_none());
+    }
+}
@@ -0,0 +1,862 @@
+//! Statistical tools for identifying and breaking classical ciphers.
+
+use std::sync::OnceLock;
+
+use crate::Cipher;
+
+/// Index of coincidence of typical English text.
+pub const ENGLISH_IOC: f64 = 0.0667;
+
+/// Index of coincidence of uniformly random letters (1/26).
+pub const RANDOM_IOC: f64 = 1.0 / 26.0;
+
+/// Relative frequencies of A-Z in typical English text.
+pub const ENGLISH_FREQUENCIES: [f64; 26] = [
+    0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015, 0.06094, 0.06966, 0.00153,
+    0.00772, 0.04025, 0.02406, 0.06749, 0.07507, 0.01929, 0.00095, 0.05987, 0.06327, 0.09056,
+    0.02758, 0.00978, 0.02360, 0.00150, 0.01974, 0.00074,
+];
+
+/// Broad families of ciphers that [`classify`] can distinguish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CipherKind {
+    /// Single-alphabet letter substitution (Caesar, Affine, Atbash).
+    Monoalphabetic,
+    /// Multiple-alphabet letter substitution (Vigenère).
+    Polyalphabetic,
+    /// Digit pairs drawn from 1-5.
+    Polybius,
+    /// Comma-separated coordinates into a planetary square.
+    MagicSquare,
+    /// Letters drawn only from A and B.
+    Baconian,
+    /// Control or non-ASCII bytes, typical of XOR output.
+    Xor,
+}
+
+/// Counts occurrences of each letter A-Z, ignoring case and non-letters.
+fn letter_counts(text: &str) -> [usize; 26] {
+    let mut counts = [0; 26];
+    for c in text.chars().filter(|c| c.is_ascii_alphabetic()) {
+        counts[(c.to_ascii_uppercase() as u8 - b'A') as usize] += 1;
+    }
+    counts
+}
+
+/// Returns the proportion of each letter A-Z among the letters of `text`.
+///
+/// Case is ignored and non-letters are skipped. All zeros if there are no letters.
+pub fn letter_frequencies(text: &str) -> [f64; 26] {
+    let counts = letter_counts(text);
+    let total: usize = counts.iter().sum();
+    let mut frequencies = [0.0; 26];
+    if total > 0 {
+        for (freq, &count) in frequencies.iter_mut().zip(&counts) {
+            *freq = count as f64 / total as f64;
+        }
+    }
+    frequencies
+}
+
+/// Width in characters of the longest bar drawn by [`histogram`].
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Renders the letter frequencies of `text` as a text bar chart.
+///
+/// Produces one line per letter A-Z, such as `E: ████████ 12.7%`. Bars are
+/// scaled relative to the most frequent letter, which gets the full width.
+pub fn histogram(text: &str) -> String {
+    let frequencies = letter_frequencies(text);
+    let max = frequencies.iter().copied().fold(0.0, f64::max);
+
+    frequencies
+        .iter()
+        .enumerate()
+        .map(|(i, &freq)| {
+            let width = if max > 0.0 {
+                (freq / max * HISTOGRAM_WIDTH as f64).round() as usize
+            } else {
+                0
+            };
+            format!(
+                "{}: {} {:.1}%",
+                (b'A' + i as u8) as char,
+                "█".repeat(width),
+                freq * 100.0
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Scores how much `text` resembles English, from 0.0 to 1.0.
+///
+/// Combines how closely the letter distribution matches
+/// [`ENGLISH_FREQUENCIES`] (one minus the total variation distance) with the
+/// share of characters that are letters or spaces, so binary noise and
+/// non-text score low.
+pub fn english_score(text: &str) -> f64 {
+    let total = text.chars().count();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let frequencies = letter_frequencies(text);
+    let distance: f64 = frequencies
+        .iter()
+        .zip(&ENGLISH_FREQUENCIES)
+        .map(|(observed, expected)| (observed - expected).abs())
+        .sum();
+    let textual = text
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic() || *c == ' ')
+        .count();
+
+    (1.0 - distance / 2.0) * (textual as f64 / total as f64)
+}
+
+/// Counts the bits that differ between two byte slices.
+///
+/// Returns `None` if the slices have different lengths. Repeating-key XOR
+/// breaking uses this to find the key size.
+pub fn hamming_distance(a: &[u8], b: &[u8]) -> Option<u32> {
+    if a.len() != b.len() {
+        return None;
+    }
+    Some(a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum())
+}
+
+/// Computes the index of coincidence of the letters in `text`.
+///
+/// This is the probability that two letters drawn at random are the same.
+/// English text scores around 0.067, uniformly random text around 0.038.
+/// Returns 0.0 when there are fewer than two letters.
+pub fn index_of_coincidence(text: &str) -> f64 {
+    let counts = letter_counts(text);
+    let total: usize = counts.iter().sum();
+    if total < 2 {
+        return 0.0;
+    }
+
+    let matches: usize = counts.iter().map(|&n| n * n.saturating_sub(1)).sum();
+    matches as f64 / (total * (total - 1)) as f64
+}
+
+/// Deals the letters of `text` into `period` columns, letter `i` going to
+/// column `i % period`.
+///
+/// For a Vigenère key of that length each column is a plain Caesar shift.
+/// Non-letters are skipped; a period of 0 gives no columns.
+pub fn columns(text: &str, period: usize) -> Vec<String> {
+    let mut columns = vec![String::new(); period];
+    if period == 0 {
+        return columns;
+    }
+    for (i, c) in text.chars().filter(char::is_ascii_alphabetic).enumerate() {
+        columns[i % period].push(c);
+    }
+    columns
+}
+
+/// Length of the shortest unit that `items` is a whole repetition of, so
+/// "KEYKEY" has period 3. An empty slice has period 0.
+pub(crate) fn repeat_period<T: PartialEq>(items: &[T]) -> usize {
+    (1..=items.len())
+        .find(|&p| {
+            items.len().is_multiple_of(p) && items.iter().zip(&items[p..]).all(|(a, b)| a == b)
+        })
+        .unwrap_or(items.len())
+}
+
+/// Counts, for each shift from 1 to `max_shift`, the letters that match
+/// the letter that many places later.
+///
+/// Non-letters are skipped and case is ignored. Under a periodic cipher
+/// such as Vigenère, letters a whole key length apart were shifted alike,
+/// so the counts peak at the period and its multiples.
+pub fn autocorrelation(text: &str, max_shift: usize) -> Vec<(usize, usize)> {
+    let letters: Vec<u8> = text
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase())
+        .collect();
+    (1..=max_shift)
+        .map(|shift| {
+            let matches = letters
+                .iter()
+                .zip(letters.iter().skip(shift))
+                .filter(|(a, b)| a == b)
+                .count();
+            (shift, matches)
+        })
+        .collect()
+}
+
+/// Character class tallies used by [`classify`].
+#[derive(Debug, Default)]
+struct CharProfile {
+    letters: usize,
+    ab_letters: usize,
+    digits: usize,
+    low_digits: usize,
+    commas: usize,
+    binary: usize,
+    total: usize,
+}
+
+impl CharProfile {
+    fn new(text: &str) -> Self {
+        let mut profile = Self::default();
+        for c in text.chars().filter(|c| !c.is_whitespace()) {
+            profile.total += 1;
+            if c.is_ascii_alphabetic() {
+                profile.letters += 1;
+                if matches!(c.to_ascii_uppercase(), 'A' | 'B') {
+                    profile.ab_letters += 1;
+                }
+            } else if c.is_ascii_digit() {
+                profile.digits += 1;
+                if ('1'..='5').contains(&c) {
+                    profile.low_digits += 1;
+                }
+            } else if c == ',' {
+                profile.commas += 1;
+            } else if c.is_control() || !c.is_ascii() {
+                profile.binary += 1;
+            }
+        }
+        profile
+    }
+
+    fn ratio(&self, count: usize) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            count as f64 / self.total as f64
+        }
+    }
+}
+
+/// Ranks likely cipher families for `ciphertext`.
+///
+/// This is a heuristic over the character set, length parity and index of
+/// coincidence. Scores fall between 0.0 and 1.0 and the result is sorted
+/// from most to least likely.
+pub fn classify(ciphertext: &str) -> Vec<(CipherKind, f64)> {
+    let profile = CharProfile::new(ciphertext);
+    let letter_ratio = profile.ratio(profile.letters);
+    let digit_ratio = profile.ratio(profile.digits);
+
+    let ioc = index_of_coincidence(ciphertext);
+    let flatness = ((ioc - RANDOM_IOC) / (ENGLISH_IOC - RANDOM_IOC)).clamp(0.0, 1.0);
+
+    let baconian = if profile.letters > 0 && profile.ab_letters == profile.letters {
+        letter_ratio
+    } else {
+        0.0
+    };
+
+    let polybius_range = if profile.digits > 0 && profile.low_digits == profile.digits {
+        1.0
+    } else {
+        0.3
+    };
+    let polybius_parity = if profile.digits.is_multiple_of(2) {
+        1.0
+    } else {
+        0.5
+    };
+
+    let has_coords = profile.commas > 0 && profile.commas * 2 <= profile.digits;
+    let magic_square = digit_ratio * if has_coords { 0.95 } else { 0.6 };
+
+    let mut ranked = vec![
+        (CipherKind::Monoalphabetic, letter_ratio * flatness * 0.9),
+        (
+            CipherKind::Polyalphabetic,
+            letter_ratio * (1.0 - flatness) * 0.9,
+        ),
+        (
+            CipherKind::Polybius,
+            digit_ratio * polybius_range * polybius_parity,
+        ),
+        (CipherKind::MagicSquare, magic_square),
+        (CipherKind::Baconian, baconian),
+        (CipherKind::Xor, profile.ratio(profile.binary)),
+    ];
+
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+/// Side-by-side comparison of two ciphers' output for the same sample.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport {
+    /// Character positions where the outputs differ, counting any length
+    /// difference as differing positions.
+    pub differing_positions: usize,
+    /// Whether both outputs have the same number of characters.
+    pub lengths_match: bool,
+    /// Index of coincidence of the first cipher's output.
+    pub ioc_a: f64,
+    /// Index of coincidence of the second cipher's output.
+    pub ioc_b: f64,
+}
+
+/// Encrypts `sample` with both ciphers and reports how the outputs differ.
+pub fn compare(a: &dyn Cipher, b: &dyn Cipher, sample: &str) -> ComparisonReport {
+    let output_a = a.encrypt(sample);
+    let output_b = b.encrypt(sample);
+
+    ComparisonReport {
+        differing_positions: differing_positions(&output_a, &output_b),
+        lengths_match: output_a.chars().count() == output_b.chars().count(),
+        ioc_a: index_of_coincidence(&output_a),
+        ioc_b: index_of_coincidence(&output_b),
+    }
+}
+
+/// Counts positions where two strings differ, including any length gap.
+fn differing_positions(a: &str, b: &str) -> usize {
+    let mismatched = a.chars().zip(b.chars()).filter(|(x, y)| x != y).count();
+    mismatched + a.chars().count().abs_diff(b.chars().count())
+}
+
+/// Key trial rate assumed by [`StrengthReport::brute_force_seconds`].
+pub const KEYS_PER_SECOND: f64 = 1e9;
+
+/// Educational summary of how hard a cipher is to attack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrengthReport {
+    /// Number of possible keys, from [`Cipher::keyspace`].
+    pub keyspace: Option<u128>,
+    /// Average fraction of the ciphertext that changes when one plaintext
+    /// letter changes; see [`avalanche`].
+    pub avalanche: f64,
+    /// Whether the cipher maps each character in place.
+    pub preserves_length: bool,
+    /// Whether encrypting and decrypting the sample give the same result.
+    pub is_reciprocal: bool,
+    /// Time to try every key at [`KEYS_PER_SECOND`].
+    pub brute_force_seconds: Option<f64>,
+}
+
+/// Summarizes keyspace, diffusion and structure of `cipher` on `sample`.
+pub fn strength_report(cipher: &dyn Cipher, sample: &str) -> StrengthReport {
+    let keyspace = cipher.keyspace();
+    StrengthReport {
+        keyspace,
+        avalanche: avalanche(cipher, sample),
+        preserves_length: cipher.preserves_length(),
+        is_reciprocal: cipher.encrypt(sample) == cipher.decrypt(sample),
+        brute_force_seconds: keyspace.map(|keys| keys as f64 / KEYS_PER_SECOND),
+    }
+}
+
+/// Measures diffusion: for each ASCII letter in `sample`, changes it to a
+/// different letter and records the fraction of ciphertext characters that
+/// change, then averages over all letters.
+///
+/// Classical substitution ciphers score about `1 / len`; a cipher with
+/// good diffusion approaches 1. Returns 0.0 if `sample` has no letters.
+pub fn avalanche(cipher: &dyn Cipher, sample: &str) -> f64 {
+    let chars: Vec<char> = sample.chars().collect();
+    let baseline = cipher.encrypt(sample);
+    let mut total = 0.0;
+    let mut trials = 0;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphabetic() {
+            continue;
+        }
+        let replacement = if c.eq_ignore_ascii_case(&'A') {
+            'B'
+        } else {
+            'A'
+        };
+        let mut altered = chars.clone();
+        altered[i] = if c.is_ascii_lowercase() {
+            replacement.to_ascii_lowercase()
+        } else {
+            replacement
+        };
+
+        let output = cipher.encrypt(&altered.iter().collect::<String>());
+        let len = output.chars().count().max(baseline.chars().count()).max(1);
+        total += differing_positions(&baseline, &output) as f64 / len as f64;
+        trials += 1;
+    }
+
+    if trials == 0 {
+        0.0
+    } else {
+        total / trials as f64
+    }
+}
+
+/// The 3000 most common quadgrams in roughly 555k letters of public-domain
+/// English from Project Gutenberg, one `QUAD COUNT` pair per line.
+///
+/// The sources are the Canterbury corpus copies of Alice's Adventures in
+/// Wonderland, As You Like It (speaker names dropped) and Paradise Lost
+/// (Gutenberg preface dropped).
+const ENGLISH_QUADGRAMS: &str = include_str!("data/english_quadgrams.txt");
+
+/// Number of distinct quadgrams over A-Z.
+const QUADGRAM_SPACE: usize = 26 * 26 * 26 * 26;
+
+/// Log-probability model of letter quadgrams used to score candidate plaintexts.
+///
+/// Scores are sums of base-10 log-probabilities over every sliding window
+/// of four letters, so higher (less negative) scores look more like the
+/// training language. Quadgrams missing from the table receive a floor
+/// probability well below the rarest known quadgram.
+#[derive(Debug, Clone)]
+pub struct NgramModel {
+    log_probs: Vec<f64>,
+}
+
+impl NgramModel {
+    /// Builds a model from quadgram counts or relative frequencies.
+    ///
+    /// Entries that are not exactly four ASCII letters are ignored.
+    pub fn from_quadgrams(data: &[(&str, f64)]) -> Self {
+        let valid: Vec<(usize, f64)> = data
+            .iter()
+            .filter_map(|&(gram, count)| Some((quadgram_index(gram)?, count)))
+            .filter(|&(_, count)| count > 0.0)
+            .collect();
+
+        let total: f64 = valid.iter().map(|&(_, count)| count).sum();
+        let floor = (0.01 / total.max(1.0)).log10();
+        let mut log_probs = vec![floor; QUADGRAM_SPACE];
+        for (index, count) in valid {
+            log_probs[index] = (count / total).log10();
+        }
+
+        Self { log_probs }
+    }
+
+    /// Returns the bundled English quadgram model, built on first use.
+    pub fn english() -> &'static Self {
+        static ENGLISH: OnceLock<NgramModel> = OnceLock::new();
+        ENGLISH.get_or_init(|| {
+            let data: Vec<(&str, f64)> = ENGLISH_QUADGRAMS
+                .lines()
+                .filter_map(|line| {
+                    let (gram, count) = line.split_once(' ')?;
+                    Some((gram, count.trim().parse().ok()?))
+                })
+                .collect();
+            Self::from_quadgrams(&data)
+        })
+    }
+
+    /// Scores the letters of `text`, ignoring case and non-letters.
+    ///
+    /// Texts with fewer than four letters score 0.0.
+    pub fn score(&self, text: &str) -> f64 {
+        let letters: Vec<usize> = text
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| (c.to_ascii_uppercase() as u8 - b'A') as usize)
+            .collect();
+
+        letters
+            .windows(4)
+            .map(|w| self.log_probs[((w[0] * 26 + w[1]) * 26 + w[2]) * 26 + w[3]])
+            .sum()
+    }
+}
+
+/// Longest Vigenère key [`solve_auto`] tries.
+const MAX_VIGENERE_PERIOD: usize = 12;
+
+/// How many of the most likely key lengths [`solve_auto`] tries to solve.
+const VIGENERE_PERIOD_CANDIDATES: usize = 3;
+
+/// A candidate decryption found by [`solve_auto`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveResult {
+    /// The cipher family the classifier suggested.
+    pub kind: CipherKind,
+    /// The specific cipher that was tried, e.g. "Caesar".
+    pub cipher: &'static str,
+    /// The recovered key, formatted for display ("" for keyless ciphers).
+    pub key: String,
+    pub plaintext: String,
+    /// [`NgramModel::score`] of the plaintext; higher is better.
+    pub score: f64,
+}
+
+/// Classifies `ciphertext` and runs the matching crackers, returning every
+/// candidate ranked by `model`, best first.
+///
+/// Monoalphabetic text is tried as Caesar, Affine and Atbash;
+/// polyalphabetic text as Vigenère at the most likely key lengths by index
+/// of coincidence; digit pairs as a standard Polybius square. Families
+/// the classifier scores at zero are skipped, and families with no
+/// cracker yet (Baconian, XOR, magic squares) yield nothing.
+pub fn solve_auto(ciphertext: &str, model: &NgramModel) -> Vec<SolveResult> {
+    let mut results = Vec::new();
+    for (kind, likelihood) in classify(ciphertext) {
+        if likelihood <= 0.0 {
+            continue;
+        }
+        let found = match kind {
+            CipherKind::Monoalphabetic => solve_monoalphabetic(ciphertext, model),
+            CipherKind::Polyalphabetic => solve_polyalphabetic(ciphertext, model),
+            CipherKind::Polybius => {
+                let plaintext = crate::Polybius::new().decrypt(ciphertext);
+                vec![("Polybius", String::new(), plaintext)]
+            }
+            _ => Vec::new(),
+        };
+        results.extend(
+            found
+                .into_iter()
+                .map(|(cipher, key, plaintext)| SolveResult {
+                    kind,
+                    cipher,
+                    key,
+                    score: model.score(&plaintext),
+                    plaintext,
+                }),
+        );
+    }
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results
+}
+
+type Candidate = (&'static str, String, String);
+
+/// Best Caesar shift and Affine key by `model`, plus Atbash.
+fn solve_monoalphabetic(ciphertext: &str, model: &NgramModel) -> Vec<Candidate> {
+    let best = |candidates: Vec<(String, String)>| {
+        candidates
+            .into_iter()
+            .map(|(key, plaintext)| (model.score(&plaintext), key, plaintext))
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, key, plaintext)| (key, plaintext))
+    };
+
+    let caesar = (0..26)
+        .map(|shift| {
+            (
+                shift.to_string(),
+                crate::Caesar::new(shift).decrypt(ciphertext),
+            )
+        })
+        .collect();
+    // a = 1 is a plain shift, already covered by Caesar.
+    let affine = crate::Affine::all_valid_keys()
+        .filter(|&(a, _)| a != 1)
+        .filter_map(|(a, b)| Some((format!("a={}, b={}", a, b), crate::Affine::new(a, b)?)))
+        .map(|(key, cipher)| (key, cipher.decrypt(ciphertext)))
+        .collect();
+
+    let mut results = Vec::new();
+    if let Some((key, plaintext)) = best(caesar) {
+        results.push(("Caesar", key, plaintext));
+    }
+    if let Some((key, plaintext)) = best(affine) {
+        results.push(("Affine", key, plaintext));
+    }
+    results.push((
+        "Atbash",
+        String::new(),
+        crate::Atbash::new().decrypt(ciphertext),
+    ));
+    results
+}
+
+/// Solves Vigenère at the key lengths whose columns look most like English.
+fn solve_polyalphabetic(ciphertext: &str, model: &NgramModel) -> Vec<Candidate> {
+    likely_periods(ciphertext)
+        .into_iter()
+        .map(|period| {
+            let key = crate::Vigenere::solve_partial(ciphertext, &vec![None; period], model);
+            let plaintext = crate::Vigenere::new(&key).decrypt(ciphertext);
+            ("Vigenère", key, plaintext)
+        })
+        .collect()
+}
+
+/// Key lengths from 2 up, ranked by the average index of coincidence of
+/// the columns they split the letters into.
+pub(crate) fn likely_periods(ciphertext: &str) -> Vec<usize> {
+    let letters = ciphertext.chars().filter(char::is_ascii_alphabetic).count();
+    let max = MAX_VIGENERE_PERIOD.min(letters / 2);
+
+    let mut periods: Vec<(usize, f64)> = (2..=max)
+        .map(|period| {
+            let total: f64 = columns(ciphertext, period)
+                .iter()
+                .map(|column| index_of_coincidence(column))
+                .sum();
+            (period, total / period as f64)
+        })
+        .collect();
+    periods.sort_by(|a, b| b.1.total_cmp(&a.1));
+    periods
+        .into_iter()
+        .take(VIGENERE_PERIOD_CANDIDATES)
+        .map(|(period, _)| period)
+        .collect()
+}
+
+/// Maps a four-letter quadgram to its dense table index.
+fn quadgram_index(gram: &str) -> Option<usize> {
+    let bytes = gram.as_bytes();
+    if bytes.len() != 4 || !bytes.iter().all(u8::is_ascii_alphabetic) {
+        return None;
+    }
+    Some(bytes.iter().fold(0, |acc, b| {
+        acc * 26 + (b.to_ascii_uppercase() - b'A') as usize
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Atbash, Caesar, MagicSquare, Polybius, Vigenere, Xor};
+
+    const SAMPLE: &str = "It was the best of times, it was the worst of times, it was the \
+        age of wisdom, it was the age of foolishness, it was the epoch of belief, it was \
+        the epoch of incredulity, it was the season of light, it was the season of darkness";
+
+    #[test]
+    fn letter_frequencies_sum_to_one() {
+        let frequencies = letter_frequencies("Hello, World!");
+        assert!((frequencies.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((frequencies[(b'L' - b'A') as usize] - 0.3).abs() < 1e-9);
+        assert_eq!(letter_frequencies("123"), [0.0; 26]);
+    }
+
+    #[test]
+    fn histogram_has_a_line_per_letter() {
+        let chart = histogram(SAMPLE);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!(lines.len(), 26);
+        assert!(lines[0].starts_with("A: "));
+        assert!(lines[25].starts_with("Z: "));
+    }
+
+    #[test]
+    fn histogram_longest_bar_is_most_frequent() {
+        let chart = histogram("EEEE TTT A");
+        let bar_len = |line: &str| line.chars().filter(|&c| c == '█').count();
+        let longest = chart.lines().max_by_key(|line| bar_len(line)).unwrap();
+        assert!(longest.starts_with("E: "));
+        assert_eq!(bar_len(longest), HISTOGRAM_WIDTH);
+        assert_eq!(histogram("123").lines().map(bar_len).sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn english_score_prefers_english() {
+        let shifted = Caesar::new(11).encrypt(SAMPLE);
+        assert!(english_score(SAMPLE) > 0.7);
+        assert!(english_score(SAMPLE) > english_score(&shifted));
+        assert!(english_score("\u{1}\u{2}\u{3}") < 0.1);
+        assert_eq!(english_score(""), 0.0);
+    }
+
+    #[test]
+    fn ioc_of_english_is_high() {
+        assert!(index_of_coincidence(SAMPLE) > 0.06);
+    }
+
+    #[test]
+    fn ioc_of_uniform_text_is_low() {
+        let text = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".repeat(4);
+        assert!(index_of_coincidence(&text) < 0.03);
+    }
+
+    #[test]
+    fn ioc_of_short_text() {
+        assert_eq!(index_of_coincidence("A"), 0.0);
+        assert_eq!(index_of_coincidence(""), 0.0);
+    }
+
+    #[test]
+    fn digits_rank_polybius_and_magic_square() {
+        let polybius = Polybius::new().encrypt("ATTACKATDAWN");
+        assert_eq!(classify(&polybius)[0].0, CipherKind::Polybius);
+
+        let magic = MagicSquare::sun().encrypt("ATTACKATDAWN");
+        assert_eq!(classify(&magic)[0].0, CipherKind::MagicSquare);
+    }
+
+    #[test]
+    fn high_ioc_ranks_monoalphabetic() {
+        let ciphertext = Caesar::new(7).encrypt(SAMPLE);
+        assert_eq!(classify(&ciphertext)[0].0, CipherKind::Monoalphabetic);
+    }
+
+    #[test]
+    fn low_ioc_ranks_polyalphabetic() {
+        let ciphertext = Vigenere::new("LEMONADE").encrypt(SAMPLE);
+        assert_eq!(classify(&ciphertext)[0].0, CipherKind::Polyalphabetic);
+    }
+
+    #[test]
+    fn ab_only_ranks_baconian() {
+        let ranked = classify("AABBB ABAAB ABBAB ABBAB ABBBA");
+        assert_eq!(ranked[0].0, CipherKind::Baconian);
+    }
+
+    #[test]
+    fn binary_ranks_xor() {
+        let ciphertext = Xor::new(&[0x80, 0x13]).encrypt("HELLO WORLD");
+        assert_eq!(classify(&ciphertext)[0].0, CipherKind::Xor);
+    }
+
+    #[test]
+    fn english_outscores_shuffled_text() {
+        let model = NgramModel::english();
+        let letters: Vec<char> = SAMPLE.chars().filter(char::is_ascii_alphabetic).collect();
+        let shuffled: String = (0..letters.len())
+            .map(|i| letters[(i * 7) % letters.len()])
+            .collect();
+        assert!(model.score(SAMPLE) > model.score(&shuffled));
+    }
+
+    #[test]
+    fn score_ignores_case_and_punctuation() {
+        let model = NgramModel::english();
+        assert_eq!(model.score("the nation"), model.score("THE-NATION!"));
+    }
+
+    #[test]
+    fn score_of_short_text() {
+        let model = NgramModel::english();
+        assert_eq!(model.score("THE"), 0.0);
+        assert_eq!(model.score(""), 0.0);
+    }
+
+    #[test]
+    fn custom_quadgram_table() {
+        let model = NgramModel::from_quadgrams(&[("ABCD", 3.0), ("BCDE", 1.0), ("bad!", 5.0)]);
+        assert!((model.score("ABCD") - 0.75f64.log10()).abs() < 1e-9);
+        assert!(model.score("ABCD") > model.score("BCDE"));
+        assert!(model.score("BCDE") > model.score("WXYZ"));
+    }
+
+    #[test]
+    fn compare_caesar_and_atbash() {
+        let report = compare(&Caesar::new(3), &Atbash::new(), "HELLO WORLD");
+        // KHOOR ZRUOG vs SVOOL DLIOW
+        assert_eq!(report.differing_positions, 7);
+        assert!(report.lengths_match);
+        assert_eq!(report.ioc_a, report.ioc_b);
+    }
+
+    #[test]
+    fn compare_counts_length_difference() {
+        let report = compare(&Caesar::new(0), &Polybius::new(), "AB");
+        assert!(!report.lengths_match);
+        assert_eq!(report.differing_positions, 4);
+    }
+
+    #[test]
+    fn compare_shows_vigenere_flattening() {
+        let report = compare(&Caesar::new(3), &Vigenere::new("LEMONADE"), SAMPLE);
+        assert!(report.ioc_a > report.ioc_b);
+    }
+
+    #[test]
+    fn strength_report_caesar() {
+        let report = strength_report(&Caesar::new(3), "ATTACK AT DAWN");
+        assert_eq!(report.keyspace, Some(26));
+        // One changed letter changes exactly one of 14 output characters.
+        assert!((report.avalanche - 1.0 / 14.0).abs() < 1e-9);
+        assert!(report.preserves_length);
+        assert!(!report.is_reciprocal);
+        assert!(report.brute_force_seconds.unwrap() < 1e-6);
+    }
+
+    #[test]
+    fn strength_report_reciprocal_ciphers() {
+        assert!(strength_report(&Atbash::new(), SAMPLE).is_reciprocal);
+        assert!(strength_report(&Caesar::rot13(), SAMPLE).is_reciprocal);
+        assert_eq!(strength_report(&Atbash::new(), SAMPLE).keyspace, Some(1));
+    }
+
+    #[test]
+    fn strength_report_unknown_keyspace() {
+        let report = strength_report(&Polybius::new(), "HELLO");
+        assert_eq!(report.keyspace, None);
+        assert_eq!(report.brute_force_seconds, None);
+        assert!(!report.preserves_length);
+    }
+
+    #[test]
+    fn avalanche_without_letters() {
+        assert_eq!(avalanche(&Caesar::new(3), "123 !"), 0.0);
+    }
+
+    #[test]
+    fn columns_deal_letters_round_robin() {
+        assert_eq!(columns("ABCDEF", 2), ["ACE", "BDF"]);
+        assert_eq!(columns("Ab, c-d e", 3), ["Ad", "be", "c"]);
+        assert!(columns("ABC", 0).is_empty());
+    }
+
+    #[test]
+    fn autocorrelation_peaks_at_period() {
+        let plaintext = "Call me Ishmael. Some years ago, never mind how long precisely, \
+            having little or no money in my purse, and nothing particular to interest me on \
+            shore, I thought I would sail about a little and see the watery part of the world. \
+            It is a way I have of driving off the spleen and regulating the circulation. \
+            Whenever I find myself growing grim about the mouth; whenever it is a damp, \
+            drizzly November in my soul; whenever I find myself involuntarily pausing before \
+            coffin warehouses, and bringing up the rear of every funeral I meet; then, I \
+            account it high time to get to sea as soon as I can.";
+        let ciphertext = Vigenere::new("LEMON").encrypt(plaintext);
+        let counts = autocorrelation(&ciphertext, 9);
+        assert_eq!(counts.len(), 9);
+        let peak = counts.iter().max_by_key(|&&(_, matches)| matches).unwrap();
+        assert_eq!(peak.0, 5);
+
+        assert_eq!(autocorrelation("AbA-b a", 3), [(1, 0), (2, 3), (3, 0)]);
+        assert!(autocorrelation("ABC", 0).is_empty());
+    }
+
+    #[test]
+    fn repeat_period_finds_smallest_unit() {
+        assert_eq!(repeat_period(b"KEYKEY"), 3);
+        assert_eq!(repeat_period(b"KEYKE"), 5);
+        assert_eq!(repeat_period(b"AAAA"), 1);
+        assert_eq!(repeat_period::<u8>(&[]), 0);
+    }
+
+    #[test]
+    fn hamming_distance_counts_bits() {
+        assert_eq!(
+            hamming_distance(b"this is a test", b"wokka wokka!!!"),
+            Some(37)
+        );
+        assert_eq!(hamming_distance(b"", b""), Some(0));
+        assert_eq!(hamming_distance(b"abc", b"ab"), None);
+    }
+
+    #[test]
+    fn solve_auto_caesar() {
+        let ciphertext = Caesar::new(7).encrypt(SAMPLE);
+        let results = solve_auto(&ciphertext, NgramModel::english());
+        let best = &results[0];
+        assert_eq!(best.kind, CipherKind::Monoalphabetic);
+        assert_eq!((best.cipher, best.key.as_str()), ("Caesar", "7"));
+        assert_eq!(best.plaintext, SAMPLE);
+        assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn solve_auto_vigenere() {
+        let ciphertext = Vigenere::new("LEMON").encrypt(SAMPLE);
+        let results = solve_auto(&ciphertext, NgramModel::english());
+        assert_eq!(results[0].cipher, "Vigenère");
+        assert_eq!(results[0].key, "LEMON");
+        assert_eq!(results[0].plaintext, SAMPLE);
+    }
+}
@@ -1,39 +1,111 @@
-use crate::Cipher;
+use crate::alphabet::AlphabetRef;
+use crate::{Alphabet, Cipher, InputClasses, Monoalphabetic};
 
 /// Atbash cipher - reverses the alphabet (A↔Z, B↔Y, etc.)
-#[derive(Debug, Clone, Copy, Default)]
-pub struct Atbash;
+#[derive(Debug, Clone, Default)]
+pub struct Atbash {
+    reverse: bool,
+    digits: bool,
+    alphabet: AlphabetRef,
+}
 
 impl Atbash {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
 
-    fn transform_char(c: char) -> char {
-        if c.is_ascii_alphabetic() {
-            let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
-            let offset = c as u8 - base;
-            (base + (25 - offset)) as char
-        } else {
-            c
+    /// Also reverses the order of the characters after substitution.
+    ///
+    /// Reversal works on `char` boundaries, so multi-byte characters stay
+    /// intact, and the combined transform is still its own inverse.
+    pub fn with_string_reversal(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Also mirrors the digits, 0↔9, 1↔8 and so on, separately from the
+    /// letters. The cipher is still its own inverse.
+    pub fn with_digits(mut self) -> Self {
+        self.digits = true;
+        self
+    }
+
+    /// Mirrors `alphabet` instead of A-Z.
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet.into();
+        self
+    }
+
+    fn transform_char(&self, c: char) -> char {
+        if self.digits && c.is_ascii_digit() {
+            return (b'9' - (c as u8 - b'0')) as char;
         }
+        let last = self.alphabet.len() as i64 - 1;
+        self.alphabet
+            .substitute(c, |i| last - i as i64)
+            .unwrap_or(c)
     }
 }
 
 impl Cipher for Atbash {
     fn encrypt(&self, input: &str) -> String {
-        input.chars().map(Self::transform_char).collect()
+        let mut output = String::with_capacity(input.len());
+        if self.reverse {
+            for c in input.chars().rev() {
+                output.push(self.transform_char(c));
+            }
+        } else {
+            for c in input.chars() {
+                output.push(self.transform_char(c));
+            }
+        }
+        output
     }
 
     fn decrypt(&self, input: &str) -> String {
         self.encrypt(input)
     }
+
+    fn preserves_length(&self) -> bool {
+        !self.reverse
+    }
+
+    fn keyspace(&self) -> Option<u128> {
+        Some(1)
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        let classes = self.alphabet.classes();
+        if self.digits {
+            classes | InputClasses::DIGITS
+        } else {
+            classes
+        }
+    }
 }
 
+impl Monoalphabetic for Atbash {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn with_digits_mirrors_digits() {
+        let cipher = Atbash::new().with_digits();
+        assert_eq!(cipher.encrypt("A1"), "Z8");
+        assert_eq!(cipher.encrypt("0123456789"), "9876543210");
+        assert_eq!(cipher.decrypt("Z8"), "A1");
+        assert_eq!(Atbash::new().encrypt("A1"), "Z1");
+    }
+
+    #[test]
+    fn with_digits_is_self_inverse() {
+        let cipher = Atbash::new().with_digits();
+        let text = "Room 101, floor 42!";
+        assert_eq!(cipher.encrypt(&cipher.encrypt(text)), text);
+    }
+
     #[test]
     fn basic_transform() {
         let cipher = Atbash::new();
@@ -61,4 +133,35 @@ mod tests {
         let cipher = Atbash::new();
         assert_eq!(cipher.encrypt("Hello, World! 123"), "Svool, Dliow! 123");
     }
+
+    #[test]
+    fn custom_alphabet() {
+        let cipher = Atbash::new().with_alphabet(Alphabet::latin_alphanumeric());
+        assert_eq!(cipher.encrypt("A0z"), "9Jk");
+        assert_eq!(cipher.decrypt("9Jk"), "A0z");
+    }
+
+    #[test]
+    fn string_reversal() {
+        let cipher = Atbash::new().with_string_reversal();
+        assert_eq!(cipher.encrypt("ABC"), "XYZ");
+        assert_eq!(cipher.encrypt("Hello, World!"), "!woilD ,loovS");
+    }
+
+    #[test]
+    fn string_reversal_is_self_inverse() {
+        let cipher = Atbash::new().with_string_reversal();
+        let original = "Zoë says: héllo → world";
+        let encrypted = cipher.encrypt(original);
+        assert_eq!(cipher.encrypt(&encrypted), original);
+        assert_eq!(cipher.decrypt(&encrypted), original);
+    }
+
+    #[test]
+    fn reversal_does_not_preserve_positions() {
+        assert!(Atbash::new().preserves_length());
+        let cipher = Atbash::new().with_string_reversal();
+        assert!(!cipher.preserves_length());
+        assert_eq!(cipher.decrypt_partial("XYZ", &[0]), "XYZ");
+    }
 }
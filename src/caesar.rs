@@ -1,14 +1,92 @@
-use crate::Cipher;
+use crate::alphabet::AlphabetRef;
+use crate::analysis;
+use crate::layout;
+use crate::{Alphabet, Cipher, InputClasses, Monoalphabetic};
 
 /// Caesar cipher - shifts each letter by a fixed amount
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Caesar {
     shift: i32,
+    digit_shift: i32,
+    line_step: i32,
+    range: Option<(u32, u32)>,
+    alphabet: AlphabetRef,
 }
 
 impl Caesar {
     pub fn new(shift: i32) -> Self {
-        Self { shift }
+        Self {
+            shift,
+            digit_shift: 0,
+            line_step: 0,
+            range: None,
+            alphabet: AlphabetRef::Latin,
+        }
+    }
+
+    /// Shifts within `alphabet` instead of A-Z.
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet.into();
+        self
+    }
+
+    /// Shifts only vowels, wrapping within A, E, I, O, U, so with shift 1
+    /// "A" becomes "E" and "U" becomes "A". Consonants pass through.
+    pub fn vowels_only(shift: i32) -> Self {
+        Self::new(shift).with_alphabet(Alphabet::vowels())
+    }
+
+    /// Shifts only consonants, wrapping within the 21 of them, so with
+    /// shift 1 "D" becomes "F" and "Z" becomes "B". Vowels pass through.
+    pub fn consonants_only(shift: i32) -> Self {
+        Self::new(shift).with_alphabet(Alphabet::consonants())
+    }
+
+    /// Shifts letters mod 26 and digits mod 10, each by its own amount.
+    pub fn with_digit_shift(letter_shift: i32, digit_shift: i32) -> Self {
+        Self {
+            digit_shift,
+            ..Self::new(letter_shift)
+        }
+    }
+
+    /// Shifts characters within the inclusive codepoint range `start..=end`,
+    /// wrapping modulo the size of the range.
+    ///
+    /// Handy for contiguous scripts such as Cyrillic А-Я. Characters outside
+    /// the range, including ASCII letters, are left alone. Returns `None` if
+    /// `start > end` or the range spans the surrogate block U+D800-U+DFFF.
+    pub fn over_range(shift: i32, start: char, end: char) -> Option<Self> {
+        let (start, end) = (start as u32, end as u32);
+        if start > end || (start <= 0xDFFF && end >= 0xD800) {
+            return None;
+        }
+        Some(Self {
+            range: Some((start, end)),
+            ..Self::new(shift)
+        })
+    }
+
+    /// Caesar with a shift derived from a `(year, month, day)` date:
+    /// `(year + month + day) mod 26`.
+    ///
+    /// The date is passed in rather than read from the clock, so a daily
+    /// puzzle can be reproduced for any day.
+    pub fn daily(seed_date: (i32, u32, u32)) -> Self {
+        let (year, month, day) = seed_date;
+        let sum = i64::from(year) + i64::from(month) + i64::from(day);
+        Self::new(sum.rem_euclid(26) as i32)
+    }
+
+    /// Shifts each line by its own amount: `start` for the first line, then
+    /// `step` more for each line after it.
+    ///
+    /// Lines are split on '\n', and the newlines are kept in the output.
+    pub fn per_line(start: i32, step: i32) -> Self {
+        Self {
+            line_step: step,
+            ..Self::new(start)
+        }
     }
 
     /// ROT13 is Caesar with shift 13
@@ -16,12 +94,90 @@ impl Caesar {
         Self::new(13)
     }
 
-    fn shift_char(&self, c: char, shift: i32) -> char {
-        if c.is_ascii_alphabetic() {
-            let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
-            let offset = (c as u8 - base) as i32;
-            let shifted = ((offset + shift).rem_euclid(26)) as u8;
-            (base + shifted) as char
+    /// Summarizes the shift for logging, e.g. "Caesar, shift 3".
+    pub fn describe(&self) -> String {
+        let mut description = format!("Caesar, shift {}", self.shift);
+        if let Some(alphabet) = self.alphabet.custom() {
+            description.push_str(&format!(", alphabet {}", alphabet));
+        }
+        if self.digit_shift != 0 {
+            description.push_str(&format!(", digit shift {}", self.digit_shift));
+        }
+        if let Some((start, end)) = self.range {
+            description.push_str(&format!(", range U+{:04X}-U+{:04X}", start, end));
+        }
+        if self.line_step != 0 {
+            description.push_str(&format!(", line step {}", self.line_step));
+        }
+        description
+    }
+
+    /// Decrypts `ciphertext`, then restores letter case from `case_pattern`
+    /// (true = uppercase), one entry per letter.
+    ///
+    /// For ciphertext whose case was normalized in transit, with the
+    /// original case sent separately.
+    pub fn decrypt_with_case(&self, ciphertext: &str, case_pattern: &[bool]) -> String {
+        layout::apply_case(&self.decrypt(ciphertext), case_pattern)
+    }
+
+    /// Shifts every byte by the shift amount modulo 256.
+    ///
+    /// Unlike `encrypt`, this is not restricted to letters: every byte
+    /// value wraps around within 0-255. Use a negated shift to reverse it.
+    pub fn shift_bytes(&self, input: &[u8]) -> Vec<u8> {
+        let shift = self.shift.rem_euclid(256) as u8;
+        input.iter().map(|b| b.wrapping_add(shift)).collect()
+    }
+
+    /// Tries all 26 shifts and returns the best one with its plaintext.
+    ///
+    /// Returns `None` unless the best plaintext's [`analysis::english_score`]
+    /// is at least `threshold`, so callers only get an answer when the
+    /// result reads convincingly like English.
+    pub fn crack_auto(ciphertext: &str, threshold: f64) -> Option<(i32, String)> {
+        (0..26)
+            .map(|shift| {
+                let plaintext = Self::new(shift).decrypt(ciphertext);
+                let score = analysis::english_score(&plaintext);
+                (shift, plaintext, score)
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .filter(|&(_, _, score)| score >= threshold)
+            .map(|(shift, plaintext, _)| (shift, plaintext))
+    }
+
+    fn shift_in_range(&self, c: char, shift: i64, start: u32, end: u32) -> char {
+        let code = c as u32;
+        if !(start..=end).contains(&code) {
+            return c;
+        }
+        let size = (end - start + 1) as i64;
+        let offset = (code - start) as i64 + shift;
+        char::from_u32(start + offset.rem_euclid(size) as u32).unwrap_or(c)
+    }
+
+    fn transform(&self, input: &str, sign: i32) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut shift = i64::from(self.shift);
+        for c in input.chars() {
+            output.push(self.shift_char(c, shift * i64::from(sign), sign));
+            if c == '\n' {
+                shift += i64::from(self.line_step);
+            }
+        }
+        output
+    }
+
+    fn shift_char(&self, c: char, shift: i64, sign: i32) -> char {
+        if let Some((start, end)) = self.range {
+            self.shift_in_range(c, shift, start, end)
+        } else if let Some(shifted) = self.alphabet.shift(c, shift) {
+            shifted
+        } else if c.is_ascii_digit() {
+            let offset = (c as u8 - b'0') as i32;
+            let shifted = ((offset + self.digit_shift * sign).rem_euclid(10)) as u8;
+            (b'0' + shifted) as char
         } else {
             c
         }
@@ -30,23 +186,75 @@ impl Caesar {
 
 impl Cipher for Caesar {
     fn encrypt(&self, input: &str) -> String {
-        input
-            .chars()
-            .map(|c| self.shift_char(c, self.shift))
-            .collect()
+        self.transform(input, 1)
     }
 
     fn decrypt(&self, input: &str) -> String {
-        input
-            .chars()
-            .map(|c| self.shift_char(c, -self.shift))
-            .collect()
+        self.transform(input, -1)
+    }
+
+    fn preserves_length(&self) -> bool {
+        true
+    }
+
+    fn keyspace(&self) -> Option<u128> {
+        match self.range {
+            Some((start, end)) => Some((end - start + 1) as u128),
+            None => Some(self.alphabet.len() as u128),
+        }
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        if let Some((start, end)) = self.range {
+            return (start..=end).filter_map(char::from_u32).collect();
+        }
+        let mut classes = self.alphabet.classes();
+        if self.digit_shift != 0 {
+            classes |= InputClasses::DIGITS;
+        }
+        classes
     }
 }
 
+impl Monoalphabetic for Caesar {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutil::{random_plaintext, ENGLISH};
+
+    #[test]
+    fn daily_shift_from_date() {
+        // 2024 + 3 + 15 = 2042 = 78 * 26 + 14
+        let cipher = Caesar::daily((2024, 3, 15));
+        assert_eq!(cipher.describe(), "Caesar, shift 14");
+        assert_eq!(cipher.encrypt("HELLO"), "VSZZC");
+        assert_eq!(cipher.decrypt("VSZZC"), "HELLO");
+        assert_eq!(Caesar::daily((-1, 0, 0)).describe(), "Caesar, shift 25");
+    }
+
+    #[test]
+    fn per_line_round_trip() {
+        let cipher = Caesar::per_line(1, 1);
+        let plaintext = "abc\nabc\nabc";
+        assert_eq!(cipher.encrypt(plaintext), "bcd\ncde\ndef");
+        assert_eq!(cipher.decrypt("bcd\ncde\ndef"), plaintext);
+        assert_eq!(cipher.describe(), "Caesar, shift 1, line step 1");
+
+        let text = "Attack at dawn\nHold the bridge\n\nRetreat!\n";
+        assert_eq!(cipher.decrypt(&cipher.encrypt(text)), text);
+    }
+
+    #[test]
+    fn decrypt_with_case_mask() {
+        let cipher = Caesar::new(3);
+        let mask = [true, false, false, false, false];
+        assert_eq!(cipher.decrypt_with_case("KHOOR", &mask), "Hello");
+        assert_eq!(
+            cipher.decrypt_with_case("KHOOR, ZRUOG!", &[true, false, false, false, false, true]),
+            "Hello, WORLD!"
+        );
+    }
 
     #[test]
     fn encrypt_shift_3() {
@@ -91,4 +299,135 @@ mod tests {
         let cipher = Caesar::new(-3);
         assert_eq!(cipher.encrypt("DEF"), "ABC");
     }
+
+    #[test]
+    fn shift_bytes_wraps_mod_256() {
+        let cipher = Caesar::new(3);
+        assert_eq!(
+            cipher.shift_bytes(&[0x00, 0x41, 0xFE]),
+            vec![0x03, 0x44, 0x01]
+        );
+    }
+
+    #[test]
+    fn shift_bytes_roundtrip() {
+        let input: Vec<u8> = (0..=255).collect();
+        let shifted = Caesar::new(300).shift_bytes(&input);
+        assert_ne!(shifted, input);
+        assert_eq!(Caesar::new(-300).shift_bytes(&shifted), input);
+    }
+
+    #[test]
+    fn digit_shift() {
+        let cipher = Caesar::with_digit_shift(3, 4);
+        let encrypted = cipher.encrypt("ABC789");
+        assert_eq!(encrypted, "DEF123");
+        assert_eq!(cipher.decrypt(&encrypted), "ABC789");
+    }
+
+    #[test]
+    fn digits_untouched_by_default() {
+        let cipher = Caesar::new(3);
+        assert_eq!(cipher.encrypt("A1"), "D1");
+    }
+
+    #[test]
+    fn describe() {
+        assert_eq!(Caesar::new(3).describe(), "Caesar, shift 3");
+        assert_eq!(
+            Caesar::with_digit_shift(3, 4).describe(),
+            "Caesar, shift 3, digit shift 4"
+        );
+        assert_eq!(
+            Caesar::over_range(1, 'А', 'Я').unwrap().describe(),
+            "Caesar, shift 1, range U+0410-U+042F"
+        );
+        assert_eq!(
+            Caesar::vowels_only(1).describe(),
+            "Caesar, shift 1, alphabet AEIOU"
+        );
+        assert_eq!(
+            Caesar::new(1).with_alphabet(Alphabet::latin()).describe(),
+            "Caesar, shift 1"
+        );
+    }
+
+    #[test]
+    fn decrypt_partial() {
+        let cipher = Caesar::new(3);
+        let ciphertext = cipher.encrypt("HELLO");
+        assert_eq!(ciphertext, "KHOOR");
+        assert_eq!(cipher.decrypt_partial(&ciphertext, &[0, 1]), "HEOOR");
+        assert_eq!(cipher.decrypt_partial(&ciphertext, &[]), "KHOOR");
+        assert_eq!(
+            cipher.decrypt_partial(&ciphertext, &[0, 1, 2, 3, 4]),
+            "HELLO"
+        );
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let alphabet = Alphabet::new("ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ")
+            .unwrap()
+            .with_case_folding();
+        let cipher = Caesar::new(3).with_alphabet(alphabet);
+        assert_eq!(cipher.encrypt("Ωαβ, AB"), "Γδε, AB");
+        assert_eq!(cipher.decrypt("Γδε, AB"), "Ωαβ, AB");
+    }
+
+    #[test]
+    fn vowels_only_wraps_within_vowels() {
+        let cipher = Caesar::vowels_only(1);
+        assert_eq!(cipher.encrypt("Quiet, Bud!"), "Qaoit, Bad!");
+        assert_eq!(cipher.decrypt("Qaoit, Bad!"), "Quiet, Bud!");
+        assert_eq!(cipher.encrypt("AEIOU"), "EIOUA");
+        assert_eq!(cipher.keyspace(), Some(5));
+    }
+
+    #[test]
+    fn consonants_only_keeps_vowels() {
+        let cipher = Caesar::consonants_only(1);
+        assert_eq!(cipher.encrypt("Dizzy"), "Fibbz");
+        assert_eq!(cipher.decrypt("Fibbz"), "Dizzy");
+        assert_eq!(cipher.keyspace(), Some(21));
+    }
+
+    #[test]
+    fn over_range_cyrillic() {
+        let cipher = Caesar::over_range(1, 'А', 'Я').unwrap();
+        assert_eq!(cipher.encrypt("АБВ"), "БВГ");
+        assert_eq!(cipher.encrypt("Я"), "А");
+        assert_eq!(cipher.encrypt("ПРИВЕТ, world"), "РСЙГЖУ, world");
+        assert_eq!(cipher.decrypt("А"), "Я");
+    }
+
+    #[test]
+    fn over_range_round_trip() {
+        let cipher = Caesar::over_range(-40, 'А', 'Я').unwrap();
+        let encrypted = cipher.encrypt("ПРИВЕТ МИР");
+        assert_eq!(cipher.decrypt(&encrypted), "ПРИВЕТ МИР");
+    }
+
+    #[test]
+    fn over_range_rejects_invalid_ranges() {
+        assert!(Caesar::over_range(1, 'Я', 'А').is_none());
+        assert!(Caesar::over_range(1, '\u{D7FF}', '\u{E000}').is_none());
+        assert!(Caesar::over_range(1, '\u{E000}', '\u{F8FF}').is_some());
+    }
+
+    #[test]
+    fn crack_auto_accepts_english() {
+        let ciphertext = Caesar::new(11).encrypt(ENGLISH);
+        assert_eq!(
+            Caesar::crack_auto(&ciphertext, 0.75),
+            Some((11, ENGLISH.to_string()))
+        );
+    }
+
+    #[test]
+    fn crack_auto_rejects_noise() {
+        let letters: Vec<char> = ('A'..='Z').collect();
+        let noise = random_plaintext(0x1234_5678, 400, &letters);
+        assert_eq!(Caesar::crack_auto(&noise, 0.75), None);
+    }
 }
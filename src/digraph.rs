@@ -0,0 +1,152 @@
+/// How a repeated letter within a single digraph is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DoubleLetterStrategy {
+    /// Insert the filler after the first letter, pushing the second into the next pair.
+    #[default]
+    Insert,
+    /// Replace the second letter of the pair with the filler.
+    Replace,
+    /// Leave repeated letters together in the same pair.
+    Allow,
+}
+
+/// Splits text into letter pairs for digraph ciphers (Playfair, Four-square, Two-square).
+///
+/// Non-letters are dropped and letters are uppercased. Repeated letters
+/// within a pair are handled according to the configured
+/// [`DoubleLetterStrategy`], and an odd trailing letter is padded with the
+/// filler.
+///
+/// Note that a doubled filler (e.g. "XX" with filler 'X') cannot be split
+/// by the filler itself and is emitted as-is.
+#[derive(Debug, Clone, Copy)]
+pub struct DigraphPrep {
+    filler: char,
+    double_letter_strategy: DoubleLetterStrategy,
+}
+
+impl Default for DigraphPrep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DigraphPrep {
+    /// Creates a digraph preparer using 'X' as the filler and inserting it between doubles.
+    pub fn new() -> Self {
+        Self {
+            filler: 'X',
+            double_letter_strategy: DoubleLetterStrategy::Insert,
+        }
+    }
+
+    /// Sets the filler used to split doubles and pad odd-length input.
+    pub fn with_filler(mut self, filler: char) -> Self {
+        self.filler = filler.to_ascii_uppercase();
+        self
+    }
+
+    /// Sets how repeated letters within a pair are handled.
+    pub fn with_double_letter_strategy(mut self, strategy: DoubleLetterStrategy) -> Self {
+        self.double_letter_strategy = strategy;
+        self
+    }
+
+    /// Splits the letters of `text` into digraphs.
+    pub fn pairs(&self, text: &str) -> Vec<(char, char)> {
+        let letters: Vec<char> = text
+            .chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        let mut pairs = Vec::with_capacity(letters.len() / 2 + 1);
+        let mut i = 0;
+
+        while i < letters.len() {
+            let first = letters[i];
+            let Some(&second) = letters.get(i + 1) else {
+                pairs.push((first, self.filler));
+                break;
+            };
+
+            if first != second || first == self.filler {
+                pairs.push((first, second));
+                i += 2;
+                continue;
+            }
+
+            match self.double_letter_strategy {
+                DoubleLetterStrategy::Insert => {
+                    pairs.push((first, self.filler));
+                    i += 1;
+                }
+                DoubleLetterStrategy::Replace => {
+                    pairs.push((first, self.filler));
+                    i += 2;
+                }
+                DoubleLetterStrategy::Allow => {
+                    pairs.push((first, second));
+                    i += 2;
+                }
+            }
+        }
+
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format_pairs(pairs: &[(char, char)]) -> String {
+        pairs
+            .iter()
+            .map(|&(a, b)| format!("{}{}", a, b))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn splits_doubles_with_filler() {
+        let prep = DigraphPrep::new();
+        assert_eq!(format_pairs(&prep.pairs("BALLOON")), "BA LX LO ON");
+    }
+
+    #[test]
+    fn pads_odd_length() {
+        let prep = DigraphPrep::new();
+        assert_eq!(format_pairs(&prep.pairs("HELLO")), "HE LX LO");
+    }
+
+    #[test]
+    fn ignores_non_letters_and_case() {
+        let prep = DigraphPrep::new();
+        assert_eq!(format_pairs(&prep.pairs("he, lp!")), "HE LP");
+    }
+
+    #[test]
+    fn custom_filler() {
+        let prep = DigraphPrep::new().with_filler('q');
+        assert_eq!(format_pairs(&prep.pairs("HELLO")), "HE LQ LO");
+    }
+
+    #[test]
+    fn replace_strategy() {
+        let prep = DigraphPrep::new().with_double_letter_strategy(DoubleLetterStrategy::Replace);
+        assert_eq!(format_pairs(&prep.pairs("BALLOON")), "BA LX OX NX");
+    }
+
+    #[test]
+    fn allow_strategy() {
+        let prep = DigraphPrep::new().with_double_letter_strategy(DoubleLetterStrategy::Allow);
+        assert_eq!(format_pairs(&prep.pairs("BALLOON")), "BA LL OO NX");
+    }
+
+    #[test]
+    fn doubled_filler_is_kept() {
+        let prep = DigraphPrep::new();
+        assert_eq!(format_pairs(&prep.pairs("XXA")), "XX AX");
+    }
+}
@@ -0,0 +1,205 @@
+//! Error types returned by fallible constructors and decoders.
+
+use std::fmt;
+
+/// Any error produced by this crate.
+///
+/// Each cipher returns its own specific error; this wraps them so callers
+/// juggling several ciphers can use `?` with a single error type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CipherError {
+    Grille(GrilleError),
+    Separator(SeparatorError),
+    Decrypt(DecryptError),
+    Vigenere(VigenereError),
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherError::Grille(err) => err.fmt(f),
+            CipherError::Separator(err) => err.fmt(f),
+            CipherError::Decrypt(err) => err.fmt(f),
+            CipherError::Vigenere(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CipherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CipherError::Grille(err) => Some(err),
+            CipherError::Separator(err) => Some(err),
+            CipherError::Decrypt(err) => Some(err),
+            CipherError::Vigenere(err) => Some(err),
+        }
+    }
+}
+
+impl From<GrilleError> for CipherError {
+    fn from(err: GrilleError) -> Self {
+        CipherError::Grille(err)
+    }
+}
+
+impl From<SeparatorError> for CipherError {
+    fn from(err: SeparatorError) -> Self {
+        CipherError::Separator(err)
+    }
+}
+
+impl From<DecryptError> for CipherError {
+    fn from(err: DecryptError) -> Self {
+        CipherError::Decrypt(err)
+    }
+}
+
+impl From<VigenereError> for CipherError {
+    fn from(err: VigenereError) -> Self {
+        CipherError::Vigenere(err)
+    }
+}
+
+/// Why a set of holes does not make a valid grille.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrilleError {
+    /// The size is zero or odd, so the grid has no clean quarter turns.
+    InvalidSize(usize),
+    /// A hole lies outside the grid.
+    OutOfBounds((usize, usize)),
+    /// A cell is exposed by more than one rotation.
+    Overlap((usize, usize)),
+    /// A cell is never exposed by any rotation.
+    Uncovered((usize, usize)),
+}
+
+impl fmt::Display for GrilleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrilleError::InvalidSize(size) => {
+                write!(f, "grille size must be even and non-zero, got {}", size)
+            }
+            GrilleError::OutOfBounds((row, col)) => {
+                write!(f, "hole ({}, {}) is outside the grid", row, col)
+            }
+            GrilleError::Overlap((row, col)) => {
+                write!(f, "cell ({}, {}) is exposed more than once", row, col)
+            }
+            GrilleError::Uncovered((row, col)) => {
+                write!(f, "cell ({}, {}) is never exposed", row, col)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GrilleError {}
+
+/// Why a pair of separators was rejected by
+/// [`MagicSquare::try_with_separators`](crate::MagicSquare::try_with_separators).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeparatorError {
+    /// One separator is a substring of the other (or empty), so decryption
+    /// could not tell where a coordinate ends.
+    Overlapping {
+        separator: String,
+        coord_separator: String,
+    },
+}
+
+impl fmt::Display for SeparatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeparatorError::Overlapping {
+                separator,
+                coord_separator,
+            } => write!(
+                f,
+                "separator {:?} and coordinate separator {:?} overlap",
+                separator, coord_separator
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SeparatorError {}
+
+/// Why [`Polybius::decrypt_checked`](crate::Polybius::decrypt_checked) rejected its input.
+///
+/// Positions are char indices into the ciphertext.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecryptError {
+    /// A coordinate digit at this position has no partner digit.
+    OddDigitRun(usize),
+    /// The coordinate starting at this position is outside the grid.
+    OutOfRange(usize),
+    /// The check digit is missing or does not match the coordinates.
+    ChecksumMismatch,
+}
+
+impl fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecryptError::OddDigitRun(position) => {
+                write!(f, "incomplete coordinate at position {}", position)
+            }
+            DecryptError::OutOfRange(position) => {
+                write!(f, "coordinate at position {} is outside the grid", position)
+            }
+            DecryptError::ChecksumMismatch => write!(f, "check digit does not match"),
+        }
+    }
+}
+
+impl std::error::Error for DecryptError {}
+
+/// Why a key was rejected by [`Vigenere::try_new`](crate::Vigenere::try_new).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VigenereError {
+    /// The key has no letters.
+    Empty,
+    /// The key contains a character outside A-Z.
+    NonAlphabetic(char),
+}
+
+impl fmt::Display for VigenereError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VigenereError::Empty => write!(f, "key is empty"),
+            VigenereError::NonAlphabetic(c) => write!(f, "key character {:?} is not a letter", c),
+        }
+    }
+}
+
+impl std::error::Error for VigenereError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grille, Vigenere};
+
+    fn build(key: &str) -> Result<Vigenere, CipherError> {
+        Ok(Vigenere::try_new(key)?)
+    }
+
+    #[test]
+    fn converts_from_constituent_errors() {
+        let err = CipherError::from(GrilleError::InvalidSize(3));
+        assert_eq!(err, CipherError::Grille(GrilleError::InvalidSize(3)));
+        assert_eq!(
+            err.to_string(),
+            "grille size must be even and non-zero, got 3"
+        );
+
+        let err: CipherError = Grille::new(0, &[]).unwrap_err().into();
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn question_mark_converts() {
+        assert_eq!(
+            build("K3Y").unwrap_err(),
+            CipherError::Vigenere(VigenereError::NonAlphabetic('3'))
+        );
+        assert!(build("KEY").is_ok());
+    }
+}
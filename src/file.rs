@@ -0,0 +1,65 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::Cipher;
+
+/// Reads `input` as UTF-8, encrypts it with `cipher` and writes `output`.
+///
+/// Fails with [`io::ErrorKind::InvalidData`] if the input is not UTF-8.
+/// For binary data use [`Xor::encrypt_file_bytes`](crate::Xor::encrypt_file_bytes).
+pub fn encrypt_file<C: Cipher + ?Sized>(cipher: &C, input: &Path, output: &Path) -> io::Result<()> {
+    let text = fs::read_to_string(input)?;
+    fs::write(output, cipher.encrypt(&text))
+}
+
+/// Reads `input` as UTF-8, decrypts it with `cipher` and writes `output`.
+pub fn decrypt_file<C: Cipher + ?Sized>(cipher: &C, input: &Path, output: &Path) -> io::Result<()> {
+    let text = fs::read_to_string(input)?;
+    fs::write(output, cipher.decrypt(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Caesar;
+
+    #[test]
+    fn caesar_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.txt");
+        let encrypted = dir.path().join("encrypted.txt");
+        let decrypted = dir.path().join("decrypted.txt");
+        fs::write(&plain, "Attack at dawn.\nBring coffee.\n").unwrap();
+
+        let cipher = Caesar::new(3);
+        encrypt_file(&cipher, &plain, &encrypted).unwrap();
+        assert_eq!(
+            fs::read_to_string(&encrypted).unwrap(),
+            "Dwwdfn dw gdzq.\nEulqj friihh.\n"
+        );
+
+        decrypt_file(&cipher, &encrypted, &decrypted).unwrap();
+        assert_eq!(
+            fs::read_to_string(&decrypted).unwrap(),
+            "Attack at dawn.\nBring coffee.\n"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("binary.bin");
+        fs::write(&input, [0xff, 0xfe, 0x00]).unwrap();
+
+        let err = encrypt_file(&Caesar::new(3), &input, &dir.path().join("out")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn missing_input_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.txt");
+        assert!(encrypt_file(&Caesar::new(3), &missing, &dir.path().join("out")).is_err());
+    }
+}
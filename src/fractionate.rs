@@ -0,0 +1,130 @@
+//! Coordinate fractionation shared by Bifid- and Trifid-style ciphers.
+//!
+//! Each letter becomes a coordinate tuple with `D` dimensions (2 for a
+//! Polybius square, 3 for a cube). Within each period block the tuples are
+//! written out one plane at a time, all first coordinates, then all second
+//! coordinates, and so on, and the resulting stream is regrouped into
+//! tuples of `D`. [`defractionate`] undoes this exactly.
+//!
+//! A period of 0 treats the whole message as a single block.
+
+/// Splits `items` into consecutive blocks of `period` (the last may be short).
+pub fn period_blocks<T>(items: &[T], period: usize) -> Vec<&[T]> {
+    if items.is_empty() {
+        Vec::new()
+    } else if period == 0 {
+        vec![items]
+    } else {
+        items.chunks(period).collect()
+    }
+}
+
+/// Separates a block of tuples into one list per coordinate dimension.
+pub fn split_planes<const D: usize>(block: &[[usize; D]]) -> [Vec<usize>; D] {
+    std::array::from_fn(|d| block.iter().map(|coords| coords[d]).collect())
+}
+
+/// Zips coordinate planes back into tuples; the inverse of [`split_planes`].
+///
+/// Stops at the end of the shortest plane.
+pub fn combine_planes<const D: usize>(planes: &[Vec<usize>; D]) -> Vec<[usize; D]> {
+    let len = planes.iter().map(Vec::len).min().unwrap_or(0);
+    (0..len)
+        .map(|i| std::array::from_fn(|d| planes[d][i]))
+        .collect()
+}
+
+/// Reads each block plane by plane and regroups the stream into tuples.
+pub fn fractionate<const D: usize>(coords: &[[usize; D]], period: usize) -> Vec<[usize; D]> {
+    period_blocks(coords, period)
+        .into_iter()
+        .flat_map(|block| {
+            let stream: Vec<usize> = split_planes(block).concat();
+            regroup(&stream)
+        })
+        .collect()
+}
+
+/// Reverses [`fractionate`] with the same period.
+pub fn defractionate<const D: usize>(coords: &[[usize; D]], period: usize) -> Vec<[usize; D]> {
+    period_blocks(coords, period)
+        .into_iter()
+        .flat_map(|block| {
+            let stream: Vec<usize> = block.iter().flatten().copied().collect();
+            let planes = std::array::from_fn(|d| stream[d * block.len()..][..block.len()].to_vec());
+            combine_planes(&planes)
+        })
+        .collect()
+}
+
+fn regroup<const D: usize>(stream: &[usize]) -> Vec<[usize; D]> {
+    stream
+        .chunks_exact(D)
+        .map(|chunk| std::array::from_fn(|d| chunk[d]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_pairs() -> Vec<[usize; 2]> {
+        (0..11).map(|i| [i % 5, (i * 3) % 5]).collect()
+    }
+
+    fn sample_triples() -> Vec<[usize; 3]> {
+        (0..11).map(|i| [i % 3, (i / 3) % 3, (i * 2) % 3]).collect()
+    }
+
+    #[test]
+    fn period_blocks_sizes() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(period_blocks(&items, 2), vec![&[1, 2][..], &[3, 4], &[5]]);
+        assert_eq!(period_blocks(&items, 0), vec![&items[..]]);
+        assert!(period_blocks::<u8>(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn split_and_combine_planes() {
+        let block = [[1, 2], [3, 4], [5, 6]];
+        let planes = split_planes(&block);
+        assert_eq!(planes, [vec![1, 3, 5], vec![2, 4, 6]]);
+        assert_eq!(combine_planes(&planes), block);
+
+        let triples = sample_triples();
+        assert_eq!(combine_planes(&split_planes(&triples)), triples);
+    }
+
+    #[test]
+    fn fractionate_whole_message() {
+        // Planes 1 3 5 / 2 4 6 read as one stream, then re-paired.
+        let coords = [[1, 2], [3, 4], [5, 6]];
+        assert_eq!(fractionate(&coords, 0), vec![[1, 3], [5, 2], [4, 6]]);
+        assert_eq!(defractionate(&[[1, 3], [5, 2], [4, 6]], 0), coords);
+    }
+
+    #[test]
+    fn round_trip_bifid_periods() {
+        let coords = sample_pairs();
+        for period in [0, 1, 2, 3, 5, 7, 11, 20] {
+            let mixed = fractionate(&coords, period);
+            assert_eq!(mixed.len(), coords.len());
+            assert_eq!(defractionate(&mixed, period), coords, "period {}", period);
+        }
+    }
+
+    #[test]
+    fn round_trip_trifid_periods() {
+        let coords = sample_triples();
+        for period in [0, 1, 2, 4, 5, 11] {
+            let mixed = fractionate(&coords, period);
+            assert_eq!(defractionate(&mixed, period), coords, "period {}", period);
+        }
+    }
+
+    #[test]
+    fn period_one_is_identity() {
+        let coords = sample_triples();
+        assert_eq!(fractionate(&coords, 1), coords);
+    }
+}
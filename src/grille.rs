@@ -0,0 +1,253 @@
+use crate::error::GrilleError;
+use crate::layout;
+use crate::Cipher;
+
+/// Letter used to fill out the last grid when the message runs short.
+const FILLER: char = 'X';
+
+/// Fleissner rotating grille - a transposition through a punched card.
+///
+/// The card is laid over an empty `size` x `size` grid and letters are
+/// written through its holes in reading order. The card is then turned 90°
+/// clockwise and the next letters are written, four times in all, and the
+/// filled grid is read out row by row. Longer messages use several grids,
+/// and the last one is padded with 'X'.
+///
+/// Only letters are transposed; other characters stay where they are.
+#[derive(Debug, Clone)]
+pub struct Grille {
+    size: usize,
+    /// Grid cell (row-major) that the k-th letter of a block is written to.
+    positions: Vec<usize>,
+}
+
+impl Grille {
+    /// Creates a grille from the `(row, col)` positions of its holes.
+    ///
+    /// The four rotations of the holes must expose every cell of the grid
+    /// exactly once, so a valid grille has `size * size / 4` holes.
+    pub fn new(size: usize, holes: &[(usize, usize)]) -> Result<Self, GrilleError> {
+        if size == 0 || !size.is_multiple_of(2) {
+            return Err(GrilleError::InvalidSize(size));
+        }
+        if let Some(&hole) = holes.iter().find(|&&(r, c)| r >= size || c >= size) {
+            return Err(GrilleError::OutOfBounds(hole));
+        }
+
+        let mut covered = vec![false; size * size];
+        let mut positions = Vec::with_capacity(size * size);
+        let mut current = holes.to_vec();
+
+        for _ in 0..4 {
+            current.sort_unstable();
+            for &(row, col) in &current {
+                let cell = row * size + col;
+                if covered[cell] {
+                    return Err(GrilleError::Overlap((row, col)));
+                }
+                covered[cell] = true;
+                positions.push(cell);
+            }
+            for hole in &mut current {
+                *hole = (hole.1, size - 1 - hole.0);
+            }
+        }
+
+        if let Some(cell) = covered.iter().position(|&c| !c) {
+            return Err(GrilleError::Uncovered((cell / size, cell % size)));
+        }
+
+        Ok(Self { size, positions })
+    }
+
+    /// Returns the side length of the grid.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Draws each filled grid for `text`'s letters, for teaching.
+    ///
+    /// Each grid is followed, on the same rows, by the turn (1-4) through
+    /// which each cell was written. Grids are read out row by row, and
+    /// successive grids are separated by a blank line.
+    ///
+    /// ```text
+    /// A I B E   1 3 1 2
+    /// ```
+    pub fn render_grid(&self, text: &str) -> String {
+        let (letters, _) = layout::strip_non_letters(text);
+        let quarter = self.positions.len() / 4;
+        let mut turns = vec![0; self.positions.len()];
+        for (k, &cell) in self.positions.iter().enumerate() {
+            turns[cell] = k / quarter + 1;
+        }
+
+        self.transpose(&letters)
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(self.positions.len())
+            .map(|grid| {
+                grid.chunks(self.size)
+                    .zip(turns.chunks(self.size))
+                    .map(|(row, turn)| format!("{}   {}", join(row), join(turn)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn blocks(&self, letters: &str) -> Vec<Vec<char>> {
+        let block_len = self.positions.len();
+        let mut letters: Vec<char> = letters.chars().collect();
+        let padded = letters.len().div_ceil(block_len) * block_len;
+        letters.resize(padded, FILLER);
+        letters.chunks(block_len).map(<[char]>::to_vec).collect()
+    }
+
+    fn transpose(&self, letters: &str) -> String {
+        let mut output = String::new();
+        for block in self.blocks(letters) {
+            let mut grid = vec![FILLER; block.len()];
+            for (&cell, &c) in self.positions.iter().zip(&block) {
+                grid[cell] = c;
+            }
+            output.extend(grid);
+        }
+        output
+    }
+
+    fn untranspose(&self, letters: &str) -> String {
+        self.blocks(letters)
+            .iter()
+            .flat_map(|block| self.positions.iter().map(|&cell| block[cell]))
+            .collect()
+    }
+}
+
+fn join<T: ToString>(items: &[T]) -> String {
+    items.iter().map(T::to_string).collect::<Vec<_>>().join(" ")
+}
+
+impl Cipher for Grille {
+    fn encrypt(&self, input: &str) -> String {
+        let (letters, fixed) = layout::strip_non_letters(input);
+        layout::restore(&self.transpose(&letters), &fixed)
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        let (letters, fixed) = layout::strip_non_letters(input);
+        layout::restore(&self.untranspose(&letters), &fixed)
+    }
+
+    /// The letters are padded with 'X' to a whole number of grids.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        let letters = input.chars().filter(|c| c.is_alphabetic()).count();
+        let block_len = self.positions.len();
+        input.len() + letters.div_ceil(block_len) * block_len - letters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOLES: [(usize, usize); 4] = [(0, 0), (0, 2), (1, 1), (3, 2)];
+
+    #[test]
+    fn encrypt_4x4() {
+        let grille = Grille::new(4, &HOLES).unwrap();
+        assert_eq!(grille.encrypt("ABCDEFGHIJKLMNOP"), "AIBEMCFNGOJHPKDL");
+    }
+
+    #[test]
+    fn round_trip_4x4() {
+        let grille = Grille::new(4, &HOLES).unwrap();
+        let encrypted = grille.encrypt("ABCDEFGHIJKLMNOP");
+        assert_eq!(grille.decrypt(&encrypted), "ABCDEFGHIJKLMNOP");
+    }
+
+    #[test]
+    fn pads_last_block() {
+        let grille = Grille::new(4, &HOLES).unwrap();
+        let encrypted = grille.encrypt("ATTACK AT DAWN");
+        assert_eq!(encrypted.chars().filter(|c| c.is_alphabetic()).count(), 16);
+        assert_eq!(grille.decrypt(&encrypted), "ATTACK AT DAWNXXXX");
+    }
+
+    #[test]
+    fn multiple_blocks() {
+        let grille = Grille::new(4, &HOLES).unwrap();
+        let plaintext = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG";
+        let encrypted = grille.encrypt(plaintext);
+        assert_eq!(encrypted.len(), 48);
+        assert!(grille.decrypt(&encrypted).starts_with(plaintext));
+    }
+
+    #[test]
+    fn render_grid_shows_turns() {
+        let grille = Grille::new(4, &HOLES).unwrap();
+        assert_eq!(
+            grille.render_grid("ABCDEFGHIJKLMNOP"),
+            "A I B E   1 3 1 2\n\
+             M C F N   4 1 2 4\n\
+             G O J H   2 4 3 2\n\
+             P K D L   4 3 1 3"
+        );
+        assert_eq!(
+            grille
+                .render_grid("ABCDEFGHIJKLMNOPQ")
+                .matches("\n\n")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn estimated_output_len_counts_padding() {
+        let grille = Grille::new(4, &HOLES).unwrap();
+        for plaintext in ["ATTACK AT DAWN", "ABCDEFGHIJKLMNOP", ""] {
+            assert_eq!(
+                grille.estimated_output_len(plaintext),
+                grille.encrypt(plaintext).len()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_sizes() {
+        assert_eq!(
+            Grille::new(0, &[]).unwrap_err(),
+            GrilleError::InvalidSize(0)
+        );
+        assert_eq!(
+            Grille::new(3, &[(0, 0), (0, 1)]).unwrap_err(),
+            GrilleError::InvalidSize(3)
+        );
+    }
+
+    #[test]
+    fn rejects_holes_outside_grid() {
+        assert_eq!(
+            Grille::new(4, &[(0, 0), (0, 4)]).unwrap_err(),
+            GrilleError::OutOfBounds((0, 4))
+        );
+    }
+
+    #[test]
+    fn rejects_overlapping_rotations() {
+        // (0, 3) is where (0, 0) lands after one turn.
+        assert_eq!(
+            Grille::new(4, &[(0, 0), (0, 3), (1, 1), (0, 1)]).unwrap_err(),
+            GrilleError::Overlap((0, 3))
+        );
+    }
+
+    #[test]
+    fn rejects_uncovered_cells() {
+        assert_eq!(
+            Grille::new(4, &[(0, 0), (0, 1), (0, 2)]).unwrap_err(),
+            GrilleError::Uncovered((1, 1))
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::ops::{BitOr, BitOrAssign};
+
+/// The kinds of character a cipher meaningfully transforms, as bit flags.
+///
+/// Returned by [`Cipher::input_classes`](crate::Cipher::input_classes) so
+/// a frontend can warn, for example, that a cipher leaves digits alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct InputClasses(u8);
+
+impl InputClasses {
+    /// No characters.
+    pub const NONE: Self = Self(0);
+    /// Alphabetic characters, in any script.
+    pub const LETTERS: Self = Self(1);
+    /// The ASCII digits 0-9.
+    pub const DIGITS: Self = Self(1 << 1);
+    /// Everything else: spaces, punctuation, symbols and control characters.
+    pub const OTHER: Self = Self(1 << 2);
+    /// Every character.
+    pub const ALL: Self = Self(Self::LETTERS.0 | Self::DIGITS.0 | Self::OTHER.0);
+
+    /// The class `c` belongs to.
+    pub fn of(c: char) -> Self {
+        if c.is_alphabetic() {
+            Self::LETTERS
+        } else if c.is_ascii_digit() {
+            Self::DIGITS
+        } else {
+            Self::OTHER
+        }
+    }
+
+    /// Returns true if every class in `other` is also in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The raw flag bits.
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl BitOr for InputClasses {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for InputClasses {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// The union of the classes of every character.
+impl FromIterator<char> for InputClasses {
+    fn from_iter<I: IntoIterator<Item = char>>(chars: I) -> Self {
+        chars
+            .into_iter()
+            .map(Self::of)
+            .fold(Self::NONE, BitOr::bitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_combine() {
+        let classes = InputClasses::LETTERS | InputClasses::DIGITS;
+        assert!(classes.contains(InputClasses::LETTERS));
+        assert!(classes.contains(InputClasses::DIGITS));
+        assert!(!classes.contains(InputClasses::OTHER));
+        assert!(InputClasses::ALL.contains(classes));
+        assert!(InputClasses::NONE.is_empty());
+        assert_eq!(InputClasses::ALL.bits(), 0b111);
+    }
+
+    #[test]
+    fn collects_from_chars() {
+        assert_eq!(
+            "ABC".chars().collect::<InputClasses>(),
+            InputClasses::LETTERS
+        );
+        assert_eq!(
+            "A1".chars().collect::<InputClasses>(),
+            InputClasses::LETTERS | InputClasses::DIGITS
+        );
+        assert_eq!(
+            "Ж!".chars().collect::<InputClasses>(),
+            InputClasses::LETTERS | InputClasses::OTHER
+        );
+        assert_eq!("".chars().collect::<InputClasses>(), InputClasses::NONE);
+    }
+}
@@ -0,0 +1,121 @@
+//! Helpers for ciphers that reorder letters but want to keep the original
+//! spacing and punctuation in place.
+//!
+//! A transposition strips the non-letters out, rearranges the letters, and
+//! then puts the non-letters back at their original positions.
+
+/// Splits `text` into its letters and the positions of everything else.
+///
+/// Positions are char indices into `text`, in ascending order.
+pub fn strip_non_letters(text: &str) -> (String, Vec<(usize, char)>) {
+    let mut letters = String::new();
+    let mut layout = Vec::new();
+
+    for (i, c) in text.chars().enumerate() {
+        if c.is_alphabetic() {
+            letters.push(c);
+        } else {
+            layout.push((i, c));
+        }
+    }
+
+    (letters, layout)
+}
+
+/// Reinserts the characters recorded by [`strip_non_letters`] among `letters`.
+///
+/// If there are more letters than the layout leaves room for (for example,
+/// padding added by a transposition), the extras are appended at the end.
+pub fn restore(letters: &str, layout: &[(usize, char)]) -> String {
+    let mut result = String::with_capacity(letters.len() + layout.len());
+    let mut letters = letters.chars();
+    let mut fixed = layout.iter().peekable();
+    let mut position = 0;
+
+    loop {
+        if let Some(&&(_, c)) = fixed.peek().filter(|&&&(i, _)| i <= position) {
+            result.push(c);
+            fixed.next();
+        } else if let Some(letter) = letters.next() {
+            result.push(letter);
+        } else if let Some(&(_, c)) = fixed.next() {
+            result.push(c);
+        } else {
+            break;
+        }
+        position += 1;
+    }
+
+    result
+}
+
+/// Sets the case of each letter in `text` from `mask` (true = uppercase).
+///
+/// The mask is consumed one entry per letter; non-letters are skipped and
+/// letters beyond the end of the mask keep their case.
+pub fn apply_case(text: &str, mask: &[bool]) -> String {
+    let mut mask = mask.iter();
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !c.is_alphabetic() {
+            result.push(c);
+            continue;
+        }
+        match mask.next() {
+            Some(true) => result.extend(c.to_uppercase()),
+            Some(false) => result.extend(c.to_lowercase()),
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_hello_world() {
+        let (letters, layout) = strip_non_letters("HELLO, WORLD!");
+        assert_eq!(letters, "HELLOWORLD");
+        assert_eq!(layout, vec![(5, ','), (6, ' '), (12, '!')]);
+    }
+
+    #[test]
+    fn restore_hello_world() {
+        let (letters, layout) = strip_non_letters("HELLO, WORLD!");
+        assert_eq!(restore(&letters, &layout), "HELLO, WORLD!");
+    }
+
+    #[test]
+    fn restore_rearranged_letters() {
+        let (_, layout) = strip_non_letters("HELLO, WORLD!");
+        assert_eq!(restore("DLROWOLLEH", &layout), "DLROW, OLLEH!");
+    }
+
+    #[test]
+    fn restore_with_padding() {
+        let (_, layout) = strip_non_letters("HI THERE.");
+        assert_eq!(restore("HITHEREXX", &layout), "HI THERE.XX");
+    }
+
+    #[test]
+    fn restore_with_missing_letters() {
+        let (_, layout) = strip_non_letters("A, B!");
+        assert_eq!(restore("A", &layout), "A, !");
+    }
+
+    #[test]
+    fn apply_case_skips_non_letters() {
+        let mask = [true, false, false, true, false];
+        assert_eq!(apply_case("ABC, DEF", &mask), "Abc, DeF");
+        assert_eq!(apply_case("abc", &[]), "abc");
+    }
+
+    #[test]
+    fn empty_and_letter_only() {
+        assert_eq!(strip_non_letters(""), (String::new(), vec![]));
+        assert_eq!(strip_non_letters("abc"), ("abc".to_string(), vec![]));
+        assert_eq!(restore("abc", &[]), "abc");
+    }
+}
@@ -0,0 +1,76 @@
+use crate::Cipher;
+
+/// Letter-to-digit pairs. Only one glyph per letter and one letter per
+/// glyph, so every substitution can be undone.
+const TABLE: [(char, char); 5] = [('A', '4'), ('E', '3'), ('I', '1'), ('O', '0'), ('S', '5')];
+
+/// Leet speak - swaps a few letters for look-alike digits.
+///
+/// Only A→4, E→3, I→1, O→0 and S→5 are used. Common extras such as T→7 or
+/// B→8 are left out to keep the set small and unambiguous. Encryption
+/// matches either case; decryption turns the digits back into uppercase
+/// letters. Digits that were already in the plaintext come back as letters,
+/// so only text without 0, 1, 3, 4 or 5 round-trips exactly.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Leet;
+
+impl Leet {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Cipher for Leet {
+    fn encrypt(&self, input: &str) -> String {
+        input
+            .chars()
+            .map(|c| {
+                let upper = c.to_ascii_uppercase();
+                TABLE
+                    .iter()
+                    .find(|&&(letter, _)| letter == upper)
+                    .map_or(c, |&(_, digit)| digit)
+            })
+            .collect()
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        input
+            .chars()
+            .map(|c| {
+                TABLE
+                    .iter()
+                    .find(|&&(_, digit)| digit == c)
+                    .map_or(c, |&(letter, _)| letter)
+            })
+            .collect()
+    }
+
+    fn preserves_length(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_elite() {
+        let cipher = Leet::new();
+        assert_eq!(cipher.encrypt("ELITE"), "3L1T3");
+        assert_eq!(cipher.decrypt("3L1T3"), "ELITE");
+    }
+
+    #[test]
+    fn matches_either_case() {
+        assert_eq!(Leet::new().encrypt("Hello, Sailor!"), "H3ll0, 541l0r!");
+        assert_eq!(Leet::new().decrypt("H3ll0, 541l0r!"), "HEllO, SAIlOr!");
+    }
+
+    #[test]
+    fn other_characters_pass_through() {
+        assert_eq!(Leet::new().encrypt("TRY 2 B 9"), "TRY 2 B 9");
+        assert_eq!(Leet::new().decrypt("TRY 2 B 9"), "TRY 2 B 9");
+    }
+}
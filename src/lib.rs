@@ -1,20 +1,364 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+pub mod analysis;
+pub mod fractionate;
+pub mod layout;
+pub mod numeric;
+#[cfg(any(test, feature = "testutil"))]
+pub mod testutil;
+
+mod adfgx;
 mod affine;
+mod alphabet;
+mod amsco;
 mod atbash;
 mod caesar;
+mod digraph;
+mod error;
+mod file;
+mod grille;
+mod input_classes;
+mod leet;
 mod magic_square;
+mod nomenclator;
+mod oracle;
+mod pattern_shift;
+mod pollux;
 mod polybius;
+mod rounds;
 mod vigenere;
 mod xor;
 
-pub use affine::Affine;
+pub use adfgx::Adfgx;
+pub use affine::{Affine, AffineKey};
+pub use alphabet::Alphabet;
+pub use amsco::Amsco;
 pub use atbash::Atbash;
 pub use caesar::Caesar;
+pub use digraph::{DigraphPrep, DoubleLetterStrategy};
+pub use error::*;
+pub use file::{decrypt_file, encrypt_file};
+pub use grille::Grille;
+pub use input_classes::InputClasses;
+pub use leet::Leet;
 pub use magic_square::{MagicSquare, Planet};
-pub use polybius::Polybius;
-pub use vigenere::Vigenere;
-pub use xor::Xor;
+pub use nomenclator::Nomenclator;
+pub use oracle::{DecryptionOracle, EncryptionOracle};
+pub use pattern_shift::PatternShift;
+pub use pollux::Pollux;
+pub use polybius::{InvalidPolicy, Polybius};
+pub use rounds::Rounds;
+pub use vigenere::{Vigenere, VigenereStream};
+pub use xor::{Xor, XorStream};
 
 pub trait Cipher {
     fn encrypt(&self, input: &str) -> String;
     fn decrypt(&self, input: &str) -> String;
+
+    /// Returns true if each output character sits at the same position as
+    /// the input character it came from, so lengths always match.
+    fn preserves_length(&self) -> bool {
+        false
+    }
+
+    /// Returns the number of possible keys for this cipher's configuration
+    /// (e.g. 26 shifts for Caesar), or `None` if it is not known.
+    fn keyspace(&self) -> Option<u128> {
+        None
+    }
+
+    /// Estimates the length in bytes of `encrypt(input)`, for sizing
+    /// buffers with `String::with_capacity`.
+    ///
+    /// The default is the input length, which is exact for ciphers that
+    /// swap one ASCII letter for another. Expanding ciphers override it.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        input.len()
+    }
+
+    /// Returns the kinds of character this cipher transforms; the rest pass
+    /// through unchanged or are dropped.
+    ///
+    /// Defaults to letters, which is what most classical ciphers work on.
+    fn input_classes(&self) -> InputClasses {
+        InputClasses::LETTERS
+    }
+
+    /// Decrypts only the characters at the given positions (char indices),
+    /// leaving the rest as ciphertext.
+    ///
+    /// Useful for progressively revealing a solution. Ciphers that do not
+    /// preserve length return the ciphertext unchanged.
+    fn decrypt_partial(&self, ciphertext: &str, reveal: &[usize]) -> String {
+        if !self.preserves_length() {
+            return ciphertext.to_string();
+        }
+
+        let plaintext: Vec<char> = self.decrypt(ciphertext).chars().collect();
+        ciphertext
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if reveal.contains(&i) { plaintext[i] } else { c })
+            .collect()
+    }
+
+    /// Encrypts `input` and reports the char indices left unchanged.
+    ///
+    /// Useful for highlighting which characters the cipher actually touched.
+    /// A letter that happens to encrypt to itself is reported too. Ciphers
+    /// that do not preserve length report no positions.
+    fn encrypt_annotated(&self, input: &str) -> (String, Vec<usize>) {
+        let ciphertext = self.encrypt(input);
+        if !self.preserves_length() {
+            return (ciphertext, Vec::new());
+        }
+
+        let unchanged = input
+            .chars()
+            .zip(ciphertext.chars())
+            .enumerate()
+            .filter(|(_, (a, b))| a == b)
+            .map(|(i, _)| i)
+            .collect();
+        (ciphertext, unchanged)
+    }
+}
+
+/// A cipher that replaces each letter with one fixed letter, whatever its
+/// position.
+pub trait Monoalphabetic: Cipher {
+    /// Returns what each of A-Z encrypts to, so `mapping()[0]` is the image
+    /// of 'A'.
+    ///
+    /// The default encrypts each letter on its own; letters the cipher
+    /// leaves alone map to themselves.
+    fn mapping(&self) -> [char; 26] {
+        std::array::from_fn(|i| {
+            let letter = (b'A' + i as u8) as char;
+            self.encrypt(&letter.to_string())
+                .chars()
+                .next()
+                .unwrap_or(letter)
+        })
+    }
+}
+
+/// Encrypts `input` with `cipher`, accepting `&str`, `String` or `&String`.
+///
+/// The trait methods take `&str` so that `Cipher` stays object-safe; this
+/// wrapper saves the explicit borrow at call sites.
+pub fn encrypt<C: Cipher + ?Sized>(cipher: &C, input: impl AsRef<str>) -> String {
+    cipher.encrypt(input.as_ref())
+}
+
+/// Decrypts `input` with `cipher`, accepting `&str`, `String` or `&String`.
+pub fn decrypt<C: Cipher + ?Sized>(cipher: &C, input: impl AsRef<str>) -> String {
+    cipher.decrypt(input.as_ref())
+}
+
+/// Encrypts only the characters whose char index falls in `range`,
+/// leaving the rest verbatim.
+///
+/// The slice is encrypted on its own, so a keyed cipher such as Vigenère
+/// starts its keystream at the first character of the range. The range is
+/// clamped to the length of `text`.
+pub fn encrypt_range<C: Cipher + ?Sized>(cipher: &C, text: &str, range: Range<usize>) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let end = range.end.min(chars.len());
+    let start = range.start.min(end);
+
+    let middle: String = chars[start..end].iter().collect();
+    let mut result: String = chars[..start].iter().collect();
+    result.push_str(&cipher.encrypt(&middle));
+    result.extend(&chars[end..]);
+    result
+}
+
+/// Encrypts each '\n'-separated line of `text` on its own and rejoins them.
+///
+/// Keyed ciphers such as Vigenère restart their keystream on every line,
+/// which suits line-oriented data like logs.
+pub fn encrypt_lines<C: Cipher + ?Sized>(cipher: &C, text: &str) -> String {
+    text.split('\n')
+        .map(|line| cipher.encrypt(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decrypts each line of `text` on its own; the inverse of [`encrypt_lines`].
+pub fn decrypt_lines<C: Cipher + ?Sized>(cipher: &C, text: &str) -> String {
+    text.split('\n')
+        .map(|line| cipher.decrypt(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Encrypts the values of the named `fields` in place, leaving keys and
+/// all other values untouched.
+///
+/// Each value is encrypted on its own, so keyed ciphers restart their
+/// keystream for every field. Names missing from `map` are ignored.
+pub fn encrypt_fields<C: Cipher + ?Sized>(
+    cipher: &C,
+    map: &mut BTreeMap<String, String>,
+    fields: &[&str],
+) {
+    for &field in fields {
+        if let Some(value) = map.get_mut(field) {
+            *value = cipher.encrypt(value);
+        }
+    }
+}
+
+/// Decrypts the values of the named `fields` in place; the inverse of
+/// [`encrypt_fields`].
+pub fn decrypt_fields<C: Cipher + ?Sized>(
+    cipher: &C,
+    map: &mut BTreeMap<String, String>,
+    fields: &[&str],
+) {
+    for &field in fields {
+        if let Some(value) = map.get_mut(field) {
+            *value = cipher.decrypt(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_selected_fields() {
+        let original: BTreeMap<String, String> = [
+            ("name", "Alice"),
+            ("city", "Paris"),
+            ("note", "Meet at noon"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let cipher = Vigenere::new("KEY");
+        let mut map = original.clone();
+        encrypt_fields(&cipher, &mut map, &["name", "note", "missing"]);
+        assert_eq!(map["name"], cipher.encrypt("Alice"));
+        assert_eq!(map["note"], cipher.encrypt("Meet at noon"));
+        assert_eq!(map["city"], "Paris");
+        assert_eq!(map.len(), 3);
+
+        decrypt_fields(&cipher, &mut map, &["name", "note"]);
+        assert_eq!(map, original);
+    }
+
+    #[test]
+    fn encrypt_range_middle_slice() {
+        let cipher = Caesar::new(3);
+        assert_eq!(encrypt_range(&cipher, "HELLO WORLD", 2..7), "HEOOR ZORLD");
+        assert_eq!(encrypt_range(&cipher, "HELLO", 3..99), "HELOR");
+        assert_eq!(encrypt_range(&cipher, "HELLO", 5..9), "HELLO");
+    }
+
+    #[test]
+    fn encrypt_range_starts_keystream_in_range() {
+        let cipher = Vigenere::new("KEY");
+        assert_eq!(encrypt_range(&cipher, "ABCHELLO", 3..8), "ABCRIJVS");
+    }
+
+    #[test]
+    fn encrypt_lines_restarts_keystream() {
+        let cipher = Vigenere::new("KEY");
+        let encrypted = encrypt_lines(&cipher, "HELLO\nHELLO\n\nHI");
+        assert_eq!(encrypted, "RIJVS\nRIJVS\n\nRM");
+        assert_ne!(cipher.encrypt("HELLO\nHELLO"), "RIJVS\nRIJVS");
+        assert_eq!(decrypt_lines(&cipher, &encrypted), "HELLO\nHELLO\n\nHI");
+    }
+
+    #[test]
+    fn input_classes_per_cipher() {
+        assert_eq!(Caesar::new(3).input_classes(), InputClasses::LETTERS);
+        assert_eq!(Xor::new(b"KEY").input_classes(), InputClasses::ALL);
+        assert_eq!(
+            Caesar::with_digit_shift(3, 5).input_classes(),
+            InputClasses::LETTERS | InputClasses::DIGITS
+        );
+        assert_eq!(
+            Vigenere::alphanumeric("KEY").input_classes(),
+            InputClasses::LETTERS | InputClasses::DIGITS
+        );
+        assert_eq!(
+            Amsco::new("KEY").unwrap().input_classes(),
+            InputClasses::LETTERS
+        );
+        assert_eq!(
+            Rounds::new(Atbash::new().with_digits(), 2).input_classes(),
+            InputClasses::LETTERS | InputClasses::DIGITS
+        );
+    }
+
+    #[test]
+    fn monoalphabetic_mappings() {
+        assert_eq!(Atbash::new().mapping()[0], 'Z');
+        assert_eq!(Caesar::new(3).mapping()[0], 'D');
+        assert_eq!(Caesar::new(3).mapping()[25], 'C');
+        assert_eq!(Affine::new(5, 8).unwrap().mapping()[1], 'N');
+        assert_eq!(
+            Atbash::new().with_string_reversal().mapping(),
+            Atbash::new().mapping()
+        );
+
+        let ciphers: [&dyn Monoalphabetic; 3] = [
+            &Caesar::rot13(),
+            &Atbash::new(),
+            &Affine::new(7, 3).unwrap(),
+        ];
+        for cipher in ciphers {
+            let mut sorted = cipher.mapping();
+            sorted.sort_unstable();
+            assert!(sorted.iter().copied().eq('A'..='Z'));
+        }
+    }
+
+    #[test]
+    fn estimated_output_len_defaults_to_input_len() {
+        let cipher = Caesar::new(3);
+        let input = "Attack at dawn!";
+        assert_eq!(cipher.estimated_output_len(input), input.len());
+        assert_eq!(
+            cipher.estimated_output_len(input),
+            cipher.encrypt(input).len()
+        );
+    }
+
+    #[test]
+    fn free_functions_accept_str_and_string() {
+        let cipher = Caesar::new(3);
+        let owned = String::from("HELLO");
+        assert_eq!(encrypt(&cipher, "HELLO"), "KHOOR");
+        assert_eq!(encrypt(&cipher, &owned), "KHOOR");
+        assert_eq!(encrypt(&cipher, owned), "KHOOR");
+        assert_eq!(decrypt(&cipher, String::from("KHOOR")), "HELLO");
+    }
+
+    #[test]
+    fn encrypt_annotated_reports_passthrough() {
+        let (ciphertext, unchanged) = Caesar::new(3).encrypt_annotated("ABC 123");
+        assert_eq!(ciphertext, "DEF 123");
+        assert_eq!(unchanged, vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn encrypt_annotated_needs_length_preservation() {
+        let (ciphertext, unchanged) = MagicSquare::saturn().encrypt_annotated("A!");
+        assert_eq!(ciphertext, "2,3!");
+        assert!(unchanged.is_empty());
+    }
+
+    #[test]
+    fn free_functions_accept_trait_objects() {
+        let cipher: Box<dyn Cipher> = Box::new(Atbash::new());
+        assert_eq!(encrypt(cipher.as_ref(), "ABC"), "ZYX");
+        assert_eq!(decrypt(&*cipher, "ZYX"), "ABC");
+    }
 }
@@ -1,3 +1,5 @@
+use crate::error::SeparatorError;
+use crate::numeric;
 use crate::Cipher;
 
 /// Planetary magic square cipher implementation.
@@ -20,6 +22,9 @@ pub struct MagicSquare {
     size: usize,
     separator: String,
     coord_separator: String,
+    letter_coords: bool,
+    zero_indexed: bool,
+    coord_width: usize,
 }
 
 /// The seven classical planetary magic squares.
@@ -35,6 +40,30 @@ pub enum Planet {
 }
 
 impl Planet {
+    /// All planets, from the smallest square to the largest.
+    pub const ALL: [Planet; 7] = [
+        Planet::Saturn,
+        Planet::Jupiter,
+        Planet::Mars,
+        Planet::Sun,
+        Planet::Venus,
+        Planet::Mercury,
+        Planet::Moon,
+    ];
+
+    /// Returns the smallest planet whose square holds `max_letter_value`
+    /// (A=1), or `None` if even the Moon's 81 cells are too few.
+    pub fn smallest_for(max_letter_value: u32) -> Option<Planet> {
+        Self::ALL
+            .into_iter()
+            .find(|planet| planet.max_value() >= max_letter_value)
+    }
+
+    /// Returns the largest value in this planet's square.
+    pub fn max_value(self) -> u32 {
+        (self.size() * self.size()) as u32
+    }
+
     /// Returns the size of this planet's magic square.
     pub fn size(self) -> usize {
         match self {
@@ -65,6 +94,9 @@ impl MagicSquare {
             size,
             separator: " ".to_string(),
             coord_separator: ",".to_string(),
+            letter_coords: false,
+            zero_indexed: false,
+            coord_width: 1,
         }
     }
 
@@ -115,6 +147,61 @@ impl MagicSquare {
         self
     }
 
+    /// Sets both separators, rejecting pairs where either is a substring of
+    /// the other.
+    ///
+    /// A separator of ", " with a coordinate separator of "," cannot be
+    /// split reliably. An empty separator counts as a substring of any
+    /// other, so it is rejected too.
+    pub fn try_with_separators(
+        mut self,
+        separator: &str,
+        coord_separator: &str,
+    ) -> Result<Self, SeparatorError> {
+        if separator.contains(coord_separator) || coord_separator.contains(separator) {
+            return Err(SeparatorError::Overlapping {
+                separator: separator.to_string(),
+                coord_separator: coord_separator.to_string(),
+            });
+        }
+        self.separator = separator.to_string();
+        self.coord_separator = coord_separator.to_string();
+        Ok(self)
+    }
+
+    /// Emits coordinates as letters (row 1 → A, column 3 → C) instead of numbers.
+    ///
+    /// Each coordinate becomes exactly two letters with no coordinate
+    /// separator, so output stays unambiguous even with an empty separator.
+    ///
+    /// A plaintext character that matches the separator and sits between
+    /// two encoded letters is read back as a separator and lost: with the
+    /// default " ", "BAD FACE" decrypts to "BADFACE".
+    pub fn with_letter_coords(mut self) -> Self {
+        self.letter_coords = true;
+        self
+    }
+
+    /// Numbers rows and columns from 0 instead of 1, for interop with tools
+    /// that expect zero-based coordinates.
+    ///
+    /// Has no effect on letter coordinates, which always start at 'A'.
+    pub fn zero_indexed(mut self) -> Self {
+        self.zero_indexed = true;
+        self
+    }
+
+    /// Zero-pads each row and column number to `width` digits, so Saturn's
+    /// "F" is written "01,03" at width 2.
+    ///
+    /// Decryption expects the same width when the coordinate separator is
+    /// empty, where "0103" would otherwise be ambiguous. A width of 0 is
+    /// treated as 1. Has no effect on letter coordinates.
+    pub fn with_coord_width(mut self, width: usize) -> Self {
+        self.coord_width = width.max(1);
+        self
+    }
+
     /// Returns the maximum letter value this square can encode (A=1).
     pub fn max_value(&self) -> u32 {
         (self.size * self.size) as u32
@@ -216,6 +303,22 @@ impl MagicSquare {
         None
     }
 
+    /// The number given to the first row and column.
+    fn origin(&self) -> usize {
+        usize::from(!self.zero_indexed)
+    }
+
+    fn decode_coords(&self, part: &str) -> Option<char> {
+        let (row, col) =
+            numeric::parse_padded_coord_pair(part, &self.coord_separator, self.coord_width)?;
+        let row = (row as usize).checked_sub(self.origin())?;
+        let col = (col as usize).checked_sub(self.origin())?;
+        if row >= self.size || col >= self.size {
+            return None;
+        }
+        Self::value_to_letter(self.square[row][col])
+    }
+
     fn letter_to_value(c: char) -> Option<u32> {
         if c.is_ascii_alphabetic() {
             Some((c.to_ascii_uppercase() as u8 - b'A' + 1) as u32)
@@ -232,71 +335,159 @@ impl MagicSquare {
         }
     }
 
-    fn encode_letter(&self, c: char) -> Option<String> {
+    /// Returns the (row, col) of each encodable letter in `text`, skipping
+    /// everything else.
+    ///
+    /// Coordinates are 1-indexed, or 0-indexed after
+    /// [`MagicSquare::zero_indexed`]; separators and letter coordinates do
+    /// not apply.
+    pub fn encode_coords(&self, text: &str) -> Vec<(usize, usize)> {
+        let origin = self.origin();
+        text.chars()
+            .filter_map(|c| self.letter_position(c))
+            .map(|(row, col)| (row + origin, col + origin))
+            .collect()
+    }
+
+    fn letter_position(&self, c: char) -> Option<(usize, usize)> {
         let value = Self::letter_to_value(c)?;
         if value > self.max_value() {
             return None;
         }
-        let (row, col) = self.find_position(value)?;
-        Some(format!("{}{}{}", row + 1, self.coord_separator, col + 1))
+        self.find_position(value)
     }
-}
 
-impl Cipher for MagicSquare {
-    fn encrypt(&self, input: &str) -> String {
-        let mut result = Vec::new();
+    fn encode_letter(&self, c: char) -> Option<String> {
+        let (row, col) = self.letter_position(c)?;
+        if self.letter_coords {
+            Some(format!("{}{}", coord_letter(row), coord_letter(col)))
+        } else {
+            let origin = self.origin();
+            let pair = ((row + origin) as u32, (col + origin) as u32);
+            Some(numeric::format_padded_coord_pair(
+                pair,
+                &self.coord_separator,
+                self.coord_width,
+            ))
+        }
+    }
 
-        for c in input.chars() {
-            if c.is_ascii_alphabetic() {
-                if let Some(encoded) = self.encode_letter(c) {
-                    result.push(encoded);
-                } else {
-                    result.push(c.to_string());
+    fn letter_coord_index(&self, c: char) -> Option<usize> {
+        if !c.is_ascii_uppercase() {
+            return None;
+        }
+        let index = (c as u8 - b'A') as usize;
+        (index < self.size).then_some(index)
+    }
+
+    fn decode_letter_pair(&self, chars: &[char]) -> Option<char> {
+        let row = self.letter_coord_index(*chars.first()?)?;
+        let col = self.letter_coord_index(*chars.get(1)?)?;
+        Self::value_to_letter(self.square[row][col])
+    }
+
+    fn decrypt_letter_coords(&self, input: &str) -> String {
+        let chars: Vec<char> = input.chars().collect();
+        let mut result = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let Some(letter) = self.decode_letter_pair(&chars[i..]) else {
+                result.push(chars[i]);
+                i += 1;
+                continue;
+            };
+
+            result.push(letter);
+            i += 2;
+
+            if let Some(len) = self.separator_at(&chars[i..]) {
+                if self.decode_letter_pair(&chars[i + len..]).is_some() {
+                    i += len;
                 }
-            } else {
-                result.push(c.to_string());
             }
         }
 
+        result
+    }
+
+    /// True when the separator is whitespace, in which case decryption
+    /// accepts any run of whitespace (spaces, tabs, newlines) in its place.
+    fn whitespace_separator(&self) -> bool {
+        !self.separator.is_empty() && self.separator.chars().all(char::is_whitespace)
+    }
+
+    /// Length of the separator at the start of `chars`, if there is one.
+    fn separator_at(&self, chars: &[char]) -> Option<usize> {
+        if self.whitespace_separator() {
+            let len = chars.iter().take_while(|c| c.is_whitespace()).count();
+            (len > 0).then_some(len)
+        } else {
+            let separator: Vec<char> = self.separator.chars().collect();
+            (!separator.is_empty() && chars.starts_with(&separator)).then_some(separator.len())
+        }
+    }
+}
+
+/// Converts a zero-based coordinate to its letter form (0 → 'A').
+fn coord_letter(index: usize) -> char {
+    (b'A' + index as u8) as char
+}
+
+impl Cipher for MagicSquare {
+    fn encrypt(&self, input: &str) -> String {
         let mut output = String::new();
-        for (i, s) in result.iter().enumerate() {
-            if i > 0 && s.contains(&self.coord_separator) {
-                if let Some(prev) = result.get(i - 1) {
-                    if prev.contains(&self.coord_separator) {
+        let mut prev_encoded = false;
+
+        for c in input.chars() {
+            match self.encode_letter(c) {
+                Some(encoded) => {
+                    if prev_encoded {
                         output.push_str(&self.separator);
                     }
+                    output.push_str(&encoded);
+                    prev_encoded = true;
+                }
+                None => {
+                    output.push(c);
+                    prev_encoded = false;
                 }
             }
-            output.push_str(s);
         }
         output
     }
 
     fn decrypt(&self, input: &str) -> String {
-        let mut result = String::new();
-        let parts: Vec<&str> = input.split(&self.separator).collect();
-
-        for part in parts {
-            if part.contains(&self.coord_separator) {
-                let coords: Vec<&str> = part.split(&self.coord_separator).collect();
-                if coords.len() == 2 {
-                    if let (Ok(row), Ok(col)) =
-                        (coords[0].parse::<usize>(), coords[1].parse::<usize>())
-                    {
-                        if row >= 1 && row <= self.size && col >= 1 && col <= self.size {
-                            let value = self.square[row - 1][col - 1];
-                            if let Some(letter) = Self::value_to_letter(value) {
-                                result.push(letter);
-                                continue;
-                            }
-                        }
-                    }
-                }
-            }
-            result.push_str(part);
+        if self.letter_coords {
+            return self.decrypt_letter_coords(input);
         }
 
-        result
+        let decode = |part: &str| match self.decode_coords(part) {
+            Some(letter) => letter.to_string(),
+            None => part.to_string(),
+        };
+        if self.whitespace_separator() {
+            input.split(char::is_whitespace).map(decode).collect()
+        } else {
+            input.split(&self.separator).map(decode).collect()
+        }
+    }
+
+    /// Squares have at most nine rows, so each coordinate is one symbol
+    /// unless padded wider.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        let coords = if self.letter_coords {
+            2
+        } else {
+            2 * self.coord_width + self.coord_separator.len()
+        };
+        input
+            .chars()
+            .map(|c| match self.letter_position(c) {
+                Some(_) => coords + self.separator.len(),
+                None => c.len_utf8(),
+            })
+            .sum()
     }
 }
 
@@ -311,6 +502,25 @@ mod tests {
         assert_eq!(cipher.encrypt("A"), "2,3");
     }
 
+    #[test]
+    fn estimated_output_len_covers_output() {
+        let cipher = MagicSquare::sun();
+        for text in ["HELLO WORLD", "A", "a-b-c", ""] {
+            assert!(cipher.estimated_output_len(text) >= cipher.encrypt(text).len());
+        }
+    }
+
+    #[test]
+    fn encode_coords_matches_encrypt() {
+        let cipher = MagicSquare::saturn();
+        assert_eq!(cipher.encode_coords("A"), vec![(2, 3)]);
+        assert_eq!(cipher.encode_coords("a, b! Z"), vec![(2, 3), (1, 1)]);
+        assert_eq!(
+            MagicSquare::saturn().zero_indexed().encode_coords("A"),
+            vec![(1, 2)]
+        );
+    }
+
     #[test]
     fn saturn_round_trip() {
         let cipher = MagicSquare::saturn();
@@ -367,6 +577,29 @@ mod tests {
         assert_eq!(cipher.decrypt(&encrypted), plaintext);
     }
 
+    #[test]
+    fn coord_width_pads_coordinates() {
+        let moon = MagicSquare::moon().with_coord_width(1);
+        let plaintext = "JUMPSOVERTHELAZYDOG";
+        assert_eq!(
+            moon.encrypt(plaintext),
+            MagicSquare::moon().encrypt(plaintext)
+        );
+        assert_eq!(moon.decrypt(&moon.encrypt(plaintext)), plaintext);
+
+        let saturn = MagicSquare::saturn().with_coord_width(2);
+        assert_eq!(saturn.encrypt("F"), "01,03");
+        assert_eq!(saturn.decrypt("01,03"), "F");
+
+        let packed = MagicSquare::moon()
+            .with_coord_separator("")
+            .with_coord_width(2);
+        let encrypted = packed.encrypt(plaintext);
+        assert!(encrypted.split(' ').all(|pair| pair.len() == 4));
+        assert_eq!(packed.decrypt(&encrypted), plaintext);
+        assert!(packed.estimated_output_len(plaintext) >= encrypted.len());
+    }
+
     #[test]
     fn letter_out_of_range() {
         let cipher = MagicSquare::saturn();
@@ -390,6 +623,32 @@ mod tests {
         assert_eq!(cipher.decrypt(&encrypted), "AB");
     }
 
+    #[test]
+    fn try_with_separators_rejects_overlap() {
+        let err = MagicSquare::saturn()
+            .try_with_separators(", ", ",")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            SeparatorError::Overlapping {
+                separator: ", ".to_string(),
+                coord_separator: ",".to_string(),
+            }
+        );
+        assert!(MagicSquare::saturn()
+            .try_with_separators("-", "--")
+            .is_err());
+        assert!(MagicSquare::saturn().try_with_separators("", ",").is_err());
+    }
+
+    #[test]
+    fn try_with_separators_accepts_distinct() {
+        let cipher = MagicSquare::sun().try_with_separators(" | ", ":").unwrap();
+        let encrypted = cipher.encrypt("HELLO");
+        assert_eq!(encrypted.matches(" | ").count(), 4);
+        assert_eq!(cipher.decrypt(&encrypted), "HELLO");
+    }
+
     #[test]
     fn custom_coord_separator() {
         let cipher = MagicSquare::saturn().with_coord_separator(".");
@@ -398,6 +657,37 @@ mod tests {
         assert_eq!(cipher.decrypt(&encrypted), "A");
     }
 
+    #[test]
+    fn letter_coords_encrypt() {
+        let cipher = MagicSquare::saturn().with_letter_coords();
+        assert_eq!(cipher.encrypt("A"), "BC");
+        assert_eq!(cipher.encrypt("AB"), "BC AA");
+    }
+
+    #[test]
+    fn letter_coords_drop_spaces_between_letters() {
+        let cipher = MagicSquare::saturn().with_letter_coords();
+        let encrypted = cipher.encrypt("BAD FACE, JZ");
+        assert_eq!(cipher.decrypt(&encrypted), "BADFACE, JZ");
+        assert_eq!(cipher.decrypt(&cipher.encrypt("BADFACE")), "BADFACE");
+    }
+
+    #[test]
+    fn letter_coords_moon_without_separators() {
+        let plaintext = "JUMPSOVERTHELAZYDOG";
+
+        let numeric = MagicSquare::moon()
+            .with_separator("")
+            .with_coord_separator("");
+        assert_ne!(numeric.decrypt(&numeric.encrypt(plaintext)), plaintext);
+
+        let lettered = MagicSquare::moon().with_letter_coords().with_separator("");
+        let encrypted = lettered.encrypt(plaintext);
+        assert_eq!(encrypted.len(), plaintext.len() * 2);
+        assert!(encrypted.chars().all(|c| ('A'..='I').contains(&c)));
+        assert_eq!(lettered.decrypt(&encrypted), plaintext);
+    }
+
     #[test]
     fn planet_sizes() {
         assert_eq!(Planet::Saturn.size(), 3);
@@ -409,6 +699,15 @@ mod tests {
         assert_eq!(Planet::Moon.size(), 9);
     }
 
+    #[test]
+    fn smallest_for_value() {
+        assert_eq!(Planet::smallest_for(26), Some(Planet::Sun));
+        assert_eq!(Planet::smallest_for(9), Some(Planet::Saturn));
+        assert_eq!(Planet::smallest_for(10), Some(Planet::Jupiter));
+        assert_eq!(Planet::smallest_for(81), Some(Planet::Moon));
+        assert_eq!(Planet::smallest_for(82), None);
+    }
+
     #[test]
     fn magic_constants() {
         assert_eq!(Planet::Saturn.magic_constant(), 15);
@@ -449,4 +748,50 @@ mod tests {
         assert_eq!(MagicSquare::mars().max_value(), 25);
         assert_eq!(MagicSquare::moon().max_value(), 81);
     }
+
+    #[test]
+    fn zero_indexed_encrypt() {
+        let cipher = MagicSquare::saturn().zero_indexed();
+        assert_eq!(cipher.encrypt("A"), "1,2");
+        assert_eq!(MagicSquare::saturn().encrypt("A"), "2,3");
+    }
+
+    #[test]
+    fn zero_indexed_round_trip() {
+        let cipher = MagicSquare::sun().zero_indexed();
+        let plaintext = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let encrypted = cipher.encrypt(plaintext);
+        assert!(encrypted.contains("0,"));
+        assert_eq!(cipher.decrypt(&encrypted), plaintext);
+    }
+
+    #[test]
+    fn zero_indexed_bounds() {
+        let cipher = MagicSquare::saturn().zero_indexed();
+        assert_eq!(cipher.decrypt("0,0"), "B");
+        assert_eq!(cipher.decrypt("3,0"), "3,0");
+        assert_eq!(MagicSquare::saturn().decrypt("0,0"), "0,0");
+    }
+
+    #[test]
+    fn decrypt_mixed_whitespace_separators() {
+        let cipher = MagicSquare::saturn();
+        let encrypted = cipher.encrypt("ABCDE");
+        assert_eq!(encrypted, "2,3 1,1 3,2 3,1 2,2");
+        assert_eq!(cipher.decrypt("2,3\t1,1 3,2\t\t3,1\n2,2"), "ABCDE");
+    }
+
+    #[test]
+    fn decrypt_mixed_whitespace_letter_coords() {
+        let cipher = MagicSquare::saturn().with_letter_coords();
+        let encrypted = cipher.encrypt("ABC");
+        assert_eq!(cipher.decrypt(&encrypted.replace(' ', "\t")), "ABC");
+    }
+
+    #[test]
+    fn non_whitespace_separator_stays_exact() {
+        let cipher = MagicSquare::saturn().with_separator("|");
+        assert_eq!(cipher.decrypt("2,3|1,1"), "AB");
+        assert_eq!(cipher.decrypt("2,3 1,1"), "2,3 1,1");
+    }
 }
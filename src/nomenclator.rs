@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use crate::{Cipher, InputClasses};
+
+/// Nomenclator - substitutes whole plaintext tokens (syllables, words or
+/// single letters) with codewords.
+///
+/// Encryption scans left to right and replaces the longest token that
+/// matches at each position; decryption does the same with the codewords.
+/// Tokens are matched exactly, including case. Text that matches no entry
+/// passes through unchanged.
+#[derive(Debug, Clone)]
+pub struct Nomenclator {
+    /// Token to codeword pairs, longest token first.
+    encode: Vec<(String, String)>,
+    /// Codeword to token pairs, longest codeword first.
+    decode: Vec<(String, String)>,
+}
+
+impl Nomenclator {
+    /// Creates a nomenclator from a table of plaintext tokens to codewords.
+    ///
+    /// Returns `None` if the table is empty, contains an empty token or
+    /// codeword, or has a codeword that is a prefix of another. Prefix-free
+    /// codewords keep decryption unambiguous: with A→"1", B→"2" and
+    /// C→"12", "AB" and "C" would both encrypt to "12".
+    pub fn new(table: HashMap<String, String>) -> Option<Self> {
+        if table.is_empty() || table.iter().any(|(t, c)| t.is_empty() || c.is_empty()) {
+            return None;
+        }
+
+        let mut encode: Vec<(String, String)> = table.into_iter().collect();
+        let mut decode: Vec<(String, String)> =
+            encode.iter().map(|(t, c)| (c.clone(), t.clone())).collect();
+
+        decode.sort_by(|a, b| a.0.cmp(&b.0));
+        // Sorted, any codeword that prefixes another also prefixes its successor.
+        if decode.windows(2).any(|w| w[1].0.starts_with(&w[0].0)) {
+            return None;
+        }
+
+        encode.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        decode.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        Some(Self { encode, decode })
+    }
+}
+
+/// Replaces the longest matching key at each position with its value.
+fn substitute(input: &str, table: &[(String, String)]) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(c) = rest.chars().next() {
+        match table
+            .iter()
+            .find(|(from, _)| rest.starts_with(from.as_str()))
+        {
+            Some((from, to)) => {
+                result.push_str(to);
+                rest = &rest[from.len()..];
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    result
+}
+
+impl Cipher for Nomenclator {
+    fn encrypt(&self, input: &str) -> String {
+        substitute(input, &self.encode)
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        substitute(input, &self.decode)
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        self.encode
+            .iter()
+            .flat_map(|(token, _)| token.chars())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> HashMap<String, String> {
+        let mut table: HashMap<String, String> = ('A'..='Z')
+            .map(|c| {
+                let code = char::from(b'A' + (c as u8 - b'A' + 1) % 26);
+                (c.to_string(), code.to_string())
+            })
+            .collect();
+        table.insert("THE".to_string(), "01".to_string());
+        table.insert("AND".to_string(), "02".to_string());
+        table
+    }
+
+    #[test]
+    fn round_trip_with_words() {
+        let cipher = Nomenclator::new(table()).unwrap();
+        let encrypted = cipher.encrypt("THE KING AND THE QUEEN");
+        assert_eq!(encrypted, "01 LJOH 02 01 RVFFO");
+        assert_eq!(cipher.decrypt(&encrypted), "THE KING AND THE QUEEN");
+    }
+
+    #[test]
+    fn prefers_longest_match() {
+        let mut table = table();
+        table.insert("TH".to_string(), "03".to_string());
+        let cipher = Nomenclator::new(table).unwrap();
+        assert_eq!(cipher.encrypt("THEN THAT"), "01O 03BU");
+        assert_eq!(cipher.decrypt("01O 03BU"), "THEN THAT");
+    }
+
+    #[test]
+    fn unmatched_text_passes_through() {
+        let cipher = Nomenclator::new(table()).unwrap();
+        assert_eq!(cipher.encrypt("the 42!"), "the 42!");
+    }
+
+    #[test]
+    fn rejects_ambiguous_tables() {
+        assert!(Nomenclator::new(HashMap::new()).is_none());
+
+        let mut duplicate = table();
+        duplicate.insert("OF".to_string(), "01".to_string());
+        assert!(Nomenclator::new(duplicate).is_none());
+
+        let mut empty = table();
+        empty.insert(String::new(), "09".to_string());
+        assert!(Nomenclator::new(empty).is_none());
+
+        let prefixed: HashMap<String, String> = [("A", "1"), ("B", "2"), ("C", "12")]
+            .into_iter()
+            .map(|(t, c)| (t.to_string(), c.to_string()))
+            .collect();
+        assert!(Nomenclator::new(prefixed).is_none());
+    }
+}
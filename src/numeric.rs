@@ -0,0 +1,105 @@
+//! Parsing and formatting of coordinate-pair ciphertext such as "2,3 1,1".
+//!
+//! Coordinate ciphers write each letter as a row and column joined by a
+//! coordinate separator, and the pairs joined by a pair separator. With an
+//! empty coordinate separator each pair is exactly two decimal digits, as
+//! in the standard Polybius square.
+
+/// Parses a single pair such as "2,3" (or "23" with an empty `coord_sep`).
+///
+/// Whitespace around each number is ignored. Returns `None` if either half
+/// is missing or is not a number.
+pub fn parse_coord_pair(s: &str, coord_sep: &str) -> Option<(u32, u32)> {
+    parse_padded_coord_pair(s, coord_sep, 1)
+}
+
+/// Parses a pair whose numbers were zero-padded to `width` digits.
+///
+/// With an empty `coord_sep` the pair must be exactly `2 * width` digits,
+/// so "0103" reads as (1, 3) at width 2. With a separator, padding is
+/// simply ignored.
+pub fn parse_padded_coord_pair(s: &str, coord_sep: &str, width: usize) -> Option<(u32, u32)> {
+    if coord_sep.is_empty() {
+        let digits: Vec<char> = s.trim().chars().collect();
+        if digits.len() != 2 * width {
+            return None;
+        }
+        return read_digit_pair(&digits, width);
+    }
+    let (row, col) = s.split_once(coord_sep)?;
+    Some((row.trim().parse().ok()?, col.trim().parse().ok()?))
+}
+
+/// Reads a pair of `width`-digit decimal numbers from the start of `chars`,
+/// ignoring anything after them.
+///
+/// Returns `None` if `width` is 0, there are too few characters, or any of
+/// them is not a digit. Fixed-width readers such as the Polybius square
+/// use this to step through unseparated ciphertext.
+pub fn read_digit_pair(chars: &[char], width: usize) -> Option<(u32, u32)> {
+    if width == 0 {
+        return None;
+    }
+    let digits = chars.get(..2 * width)?;
+    let number = |half: &[char]| {
+        half.iter()
+            .try_fold(0, |value, c| Some(value * 10 + c.to_digit(10)?))
+    };
+    Some((number(&digits[..width])?, number(&digits[width..])?))
+}
+
+/// Formats one pair as row, `coord_sep`, column.
+pub fn format_coord_pair(pair: (u32, u32), coord_sep: &str) -> String {
+    format_padded_coord_pair(pair, coord_sep, 1)
+}
+
+/// Formats one pair with each number zero-padded to `width` digits.
+pub fn format_padded_coord_pair((row, col): (u32, u32), coord_sep: &str, width: usize) -> String {
+    format!("{:0w$}{}{:0w$}", row, coord_sep, col, w = width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single_pair() {
+        assert_eq!(parse_coord_pair("2,3", ","), Some((2, 3)));
+        assert_eq!(parse_coord_pair(" 12 , 7 ", ","), Some((12, 7)));
+        assert_eq!(parse_coord_pair("23", ""), Some((2, 3)));
+    }
+
+    #[test]
+    fn parse_malformed_pair() {
+        assert_eq!(parse_coord_pair("", ","), None);
+        assert_eq!(parse_coord_pair("2", ","), None);
+        assert_eq!(parse_coord_pair("2,", ","), None);
+        assert_eq!(parse_coord_pair(",3", ","), None);
+        assert_eq!(parse_coord_pair("a,3", ","), None);
+        assert_eq!(parse_coord_pair("-1,3", ","), None);
+        assert_eq!(parse_coord_pair("2;3", ","), None);
+        assert_eq!(parse_coord_pair("2", ""), None);
+        assert_eq!(parse_coord_pair("234", ""), None);
+    }
+
+    #[test]
+    fn read_digit_pair_from_prefix() {
+        let chars: Vec<char> = "0203x".chars().collect();
+        assert_eq!(read_digit_pair(&chars, 1), Some((0, 2)));
+        assert_eq!(read_digit_pair(&chars, 2), Some((2, 3)));
+        assert_eq!(read_digit_pair(&chars, 3), None);
+        assert_eq!(read_digit_pair(&chars, 0), None);
+        assert_eq!(read_digit_pair(&['2'], 1), None);
+    }
+
+    #[test]
+    fn padded_pairs() {
+        assert_eq!(format_coord_pair((2, 3), ","), "2,3");
+        assert_eq!(format_padded_coord_pair((1, 3), ",", 2), "01,03");
+        assert_eq!(format_padded_coord_pair((12, 3), "", 2), "1203");
+        assert_eq!(parse_padded_coord_pair("01,03", ",", 2), Some((1, 3)));
+        assert_eq!(parse_padded_coord_pair("0103", "", 2), Some((1, 3)));
+        assert_eq!(parse_padded_coord_pair("103", "", 2), None);
+        assert_eq!(parse_padded_coord_pair("", "", 0), None);
+    }
+}
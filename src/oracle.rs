@@ -0,0 +1,102 @@
+use std::fmt;
+
+use crate::Cipher;
+
+/// Wraps a secret cipher and exposes only encryption.
+///
+/// Useful for demonstrating chosen-plaintext attacks: callers can submit
+/// plaintexts and observe ciphertexts, but never see the key. `Debug`
+/// output is redacted for the same reason.
+#[derive(Clone)]
+pub struct EncryptionOracle<C> {
+    cipher: C,
+}
+
+impl<C: Cipher> EncryptionOracle<C> {
+    /// Hides `cipher` behind an encrypt-only interface.
+    pub fn new(cipher: C) -> Self {
+        Self { cipher }
+    }
+
+    /// Encrypts a chosen plaintext with the secret cipher.
+    pub fn encrypt(&self, plaintext: &str) -> String {
+        self.cipher.encrypt(plaintext)
+    }
+}
+
+impl<C> fmt::Debug for EncryptionOracle<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptionOracle").finish_non_exhaustive()
+    }
+}
+
+/// Wraps a secret cipher and exposes only decryption.
+///
+/// Useful for demonstrating chosen-ciphertext attacks: callers can submit
+/// ciphertexts and observe plaintexts, but never see the key. `Debug`
+/// output is redacted for the same reason.
+#[derive(Clone)]
+pub struct DecryptionOracle<C> {
+    cipher: C,
+}
+
+impl<C: Cipher> DecryptionOracle<C> {
+    /// Hides `cipher` behind a decrypt-only interface.
+    pub fn new(cipher: C) -> Self {
+        Self { cipher }
+    }
+
+    /// Decrypts a chosen ciphertext with the secret cipher.
+    pub fn decrypt(&self, ciphertext: &str) -> String {
+        self.cipher.decrypt(ciphertext)
+    }
+}
+
+impl<C> fmt::Debug for DecryptionOracle<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecryptionOracle").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Affine, Caesar};
+
+    fn letter_value(text: &str) -> i32 {
+        (text.as_bytes()[0] - b'A') as i32
+    }
+
+    #[test]
+    fn recovers_affine_key_with_chosen_plaintext() {
+        let secret = Affine::new(7, 11).unwrap();
+        let oracle = EncryptionOracle::new(secret.clone());
+
+        // E(A) = b and E(B) = a + b, so two queries reveal the whole key.
+        let b = letter_value(&oracle.encrypt("A"));
+        let a = (letter_value(&oracle.encrypt("B")) - b).rem_euclid(26);
+        assert_eq!((a, b), (7, 11));
+
+        let recovered = Affine::new(a, b).unwrap();
+        let ciphertext = secret.encrypt("ATTACK AT DAWN");
+        assert_eq!(recovered.decrypt(&ciphertext), "ATTACK AT DAWN");
+    }
+
+    #[test]
+    fn recovers_caesar_shift_with_chosen_ciphertext() {
+        let oracle = DecryptionOracle::new(Caesar::new(19));
+        let shift = (b'A' as i32 - oracle.decrypt("A").as_bytes()[0] as i32).rem_euclid(26);
+        assert_eq!(shift, 19);
+    }
+
+    #[test]
+    fn debug_hides_the_key() {
+        let cipher = Affine::new(7, 11).unwrap();
+        assert!(format!("{:?}", cipher).contains("11"));
+
+        let encrypting = format!("{:?}", EncryptionOracle::new(cipher.clone()));
+        let decrypting = format!("{:?}", DecryptionOracle::new(cipher));
+        assert_eq!(encrypting, "EncryptionOracle { .. }");
+        assert_eq!(decrypting, "DecryptionOracle { .. }");
+    }
+}
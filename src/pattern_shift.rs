@@ -0,0 +1,126 @@
+use crate::{Alphabet, Cipher};
+
+/// Pattern shift cipher - each letter is shifted by the next amount in a
+/// repeating pattern such as `[1, -1, 2]`.
+///
+/// Like Vigenère, the pattern only advances on letters. Unlike Gronsfeld,
+/// shifts may be negative or larger than 9. Decryption applies the negated
+/// pattern.
+#[derive(Debug, Clone)]
+pub struct PatternShift {
+    shifts: Vec<i32>,
+    alphabet: Alphabet,
+    ignored: Vec<char>,
+}
+
+impl PatternShift {
+    /// Creates a cipher cycling through `shifts`. An empty pattern leaves
+    /// text unchanged.
+    pub fn new(shifts: &[i32]) -> Self {
+        Self {
+            shifts: shifts.to_vec(),
+            alphabet: Alphabet::latin(),
+            ignored: Vec::new(),
+        }
+    }
+
+    /// Passes `chars` through untouched without advancing the pattern, even
+    /// if they are letters.
+    pub fn ignoring(mut self, chars: &[char]) -> Self {
+        self.ignored = chars.to_vec();
+        self
+    }
+
+    fn transform(&self, input: &str, decrypt: bool) -> String {
+        if self.shifts.is_empty() {
+            return input.to_string();
+        }
+
+        let mut pattern = self.shifts.iter().cycle();
+        input
+            .chars()
+            .map(|c| {
+                if self.ignored.contains(&c) || self.alphabet.index_of(c).is_none() {
+                    return c;
+                }
+                let shift = i64::from(*pattern.next().unwrap_or(&0));
+                let shift = if decrypt { -shift } else { shift };
+                self.alphabet.shift(c, shift).unwrap_or(c)
+            })
+            .collect()
+    }
+}
+
+impl Cipher for PatternShift {
+    fn encrypt(&self, input: &str) -> String {
+        self.transform(input, false)
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        self.transform(input, true)
+    }
+
+    fn preserves_length(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Caesar;
+
+    #[test]
+    fn encrypt_basic() {
+        let cipher = PatternShift::new(&[1, -1, 2]);
+        assert_eq!(cipher.encrypt("AAAA"), "BZCB");
+        assert_eq!(cipher.encrypt("Hi, there"), "Ih, vidtf");
+    }
+
+    #[test]
+    fn round_trip_matches_manual_shifts() {
+        let pattern = [1, -1, 2];
+        let cipher = PatternShift::new(&pattern);
+        let plaintext = "The quick brown fox jumps over the lazy dog!";
+
+        let mut letters = 0;
+        let manual: String = plaintext
+            .chars()
+            .map(|c| {
+                if !c.is_ascii_alphabetic() {
+                    return c;
+                }
+                let shift = pattern[letters % pattern.len()];
+                letters += 1;
+                Caesar::new(shift)
+                    .encrypt(&c.to_string())
+                    .chars()
+                    .next()
+                    .unwrap()
+            })
+            .collect();
+
+        let encrypted = cipher.encrypt(plaintext);
+        assert_eq!(encrypted, manual);
+        assert_eq!(cipher.decrypt(&encrypted), plaintext);
+    }
+
+    #[test]
+    fn large_shifts_wrap() {
+        let cipher = PatternShift::new(&[27, -53]);
+        assert_eq!(cipher.encrypt("AB"), "BA");
+        assert_eq!(cipher.decrypt("BA"), "AB");
+    }
+
+    #[test]
+    fn ignoring_letters_keeps_pattern_position() {
+        let cipher = PatternShift::new(&[1, 2]).ignoring(&['X']);
+        assert_eq!(cipher.encrypt("AXA"), "BXC");
+        assert_eq!(cipher.decrypt("BXC"), "AXA");
+    }
+
+    #[test]
+    fn empty_pattern_is_identity() {
+        assert_eq!(PatternShift::new(&[]).encrypt("Hello"), "Hello");
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{Cipher, InputClasses};
+
+/// International Morse code for letters and digits.
+const MORSE: [(char, &str); 36] = [
+    ('A', ".-"),
+    ('B', "-..."),
+    ('C', "-.-."),
+    ('D', "-.."),
+    ('E', "."),
+    ('F', "..-."),
+    ('G', "--."),
+    ('H', "...."),
+    ('I', ".."),
+    ('J', ".---"),
+    ('K', "-.-"),
+    ('L', ".-.."),
+    ('M', "--"),
+    ('N', "-."),
+    ('O', "---"),
+    ('P', ".--."),
+    ('Q', "--.-"),
+    ('R', ".-."),
+    ('S', "..."),
+    ('T', "-"),
+    ('U', "..-"),
+    ('V', "...-"),
+    ('W', ".--"),
+    ('X', "-..-"),
+    ('Y', "-.--"),
+    ('Z', "--.."),
+    ('0', "-----"),
+    ('1', ".----"),
+    ('2', "..---"),
+    ('3', "...--"),
+    ('4', "....-"),
+    ('5', "....."),
+    ('6', "-...."),
+    ('7', "--..."),
+    ('8', "---.."),
+    ('9', "----."),
+];
+
+/// Pollux cipher - Morse code with each symbol disguised as a digit.
+///
+/// Plaintext is first converted to Morse code, with `x` separating letters
+/// and `xx` separating words. Each dot, dash and separator is then replaced
+/// by one of the digits the key assigns to it. When several digits share a
+/// symbol they are used in rotation, so repeated symbols vary in the output.
+///
+/// Characters without a Morse representation are dropped, and decryption
+/// produces uppercase text with single spaces between words.
+#[derive(Debug, Clone)]
+pub struct Pollux {
+    symbols: [char; 10],
+}
+
+impl Pollux {
+    /// Creates a Pollux cipher from a 10-character mapping.
+    ///
+    /// The character at position `d` is the Morse symbol for digit `d`, and
+    /// must be `.`, `-` or `x`. Returns `None` if the mapping has the wrong
+    /// length, contains another character, or leaves a symbol unused.
+    pub fn new(mapping: &str) -> Option<Self> {
+        let chars: Vec<char> = mapping.chars().collect();
+        let symbols: [char; 10] = chars.try_into().ok()?;
+
+        if symbols.iter().any(|c| !matches!(c, '.' | '-' | 'x')) {
+            return None;
+        }
+        if ['.', '-', 'x'].iter().any(|s| !symbols.contains(s)) {
+            return None;
+        }
+
+        Some(Self { symbols })
+    }
+
+    fn to_morse(input: &str) -> String {
+        input
+            .split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .filter_map(|c| morse_for(c.to_ascii_uppercase()))
+                    .collect::<Vec<_>>()
+                    .join("x")
+            })
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("xx")
+    }
+
+    fn from_morse(morse: &str) -> String {
+        morse
+            .split("xx")
+            .map(|word| word.split('x').filter_map(char_for).collect::<String>())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn digits_for(&self, symbol: char) -> Vec<char> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|&(_, &s)| s == symbol)
+            .map(|(d, _)| (b'0' + d as u8) as char)
+            .collect()
+    }
+}
+
+fn morse_for(c: char) -> Option<&'static str> {
+    MORSE
+        .iter()
+        .find(|&&(ch, _)| ch == c)
+        .map(|&(_, code)| code)
+}
+
+fn char_for(code: &str) -> Option<char> {
+    MORSE.iter().find(|&&(_, m)| m == code).map(|&(ch, _)| ch)
+}
+
+impl Cipher for Pollux {
+    fn encrypt(&self, input: &str) -> String {
+        let candidates = [
+            self.digits_for('.'),
+            self.digits_for('-'),
+            self.digits_for('x'),
+        ];
+        let mut counters = [0usize; 3];
+
+        Self::to_morse(input)
+            .chars()
+            .map(|symbol| {
+                let slot = match symbol {
+                    '.' => 0,
+                    '-' => 1,
+                    _ => 2,
+                };
+                let digits = &candidates[slot];
+                let digit = digits[counters[slot] % digits.len()];
+                counters[slot] += 1;
+                digit
+            })
+            .collect()
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        let morse: String = input
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| self.symbols[d as usize])
+            .collect();
+        Self::from_morse(&morse)
+    }
+
+    /// Upper bound: at most five Morse symbols plus a separator per character.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        input.chars().count() * 6
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        InputClasses::LETTERS | InputClasses::DIGITS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAPPING: &str = "..-x.-x.-x";
+
+    #[test]
+    fn sos_round_trip() {
+        let cipher = Pollux::new(MAPPING).unwrap();
+        let encrypted = cipher.encrypt("SOS");
+        assert!(encrypted.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(cipher.decrypt(&encrypted), "SOS");
+    }
+
+    #[test]
+    fn encrypt_rotates_digits() {
+        let cipher = Pollux::new(MAPPING).unwrap();
+        // ...x---x... with dots from [0,1,4,7], dashes [2,5,8], separators [3,6,9]
+        assert_eq!(cipher.encrypt("SOS"), "01432586701");
+    }
+
+    #[test]
+    fn multi_word_round_trip() {
+        let cipher = Pollux::new("x.-.-x.-x.").unwrap();
+        let encrypted = cipher.encrypt("Meet me at 10");
+        assert_eq!(cipher.decrypt(&encrypted), "MEET ME AT 10");
+    }
+
+    #[test]
+    fn drops_unencodable_characters() {
+        let cipher = Pollux::new(MAPPING).unwrap();
+        let encrypted = cipher.encrypt("S.O.S!");
+        assert_eq!(cipher.decrypt(&encrypted), "SOS");
+    }
+
+    #[test]
+    fn decrypt_ignores_non_digits() {
+        let cipher = Pollux::new(MAPPING).unwrap();
+        assert_eq!(cipher.decrypt("01432 58670 1"), "SOS");
+    }
+
+    #[test]
+    fn invalid_mappings() {
+        assert!(Pollux::new("..-x.-x.-").is_none());
+        assert!(Pollux::new("..-x.-x.-?").is_none());
+        assert!(Pollux::new("..-..-.--.").is_none());
+    }
+}
@@ -1,10 +1,16 @@
-use crate::Cipher;
+use crate::error::DecryptError;
+use crate::numeric;
+use crate::{Cipher, InputClasses};
 
 /// Polybius square cipher implementation.
 ///
 /// Uses a 5x5 grid where I/J are combined. Each letter is encoded as
 /// a two-digit coordinate (row, column) using digits 1-5.
 ///
+/// Grids from 2x2 up to 9x9 can be built from custom alphabets, e.g. a
+/// 6x6 grid of A-Z and 0-9 that needs no I/J merge. Coordinates then use
+/// digits 1 through the grid size.
+///
 /// Standard grid:
 /// ```text
 ///   1 2 3 4 5
@@ -16,10 +22,40 @@ use crate::Cipher;
 /// ```
 #[derive(Debug, Clone)]
 pub struct Polybius {
-    grid: [[char; 5]; 5],
+    grid: Vec<Vec<char>>,
+    size: usize,
     separator: String,
+    invalid_policy: InvalidPolicy,
+    radix: Option<u32>,
+    preserve_words: bool,
+    /// A letter with no cell of its own, and the letter it is encoded as.
+    merge: (char, char),
+    column_major: bool,
+    checksum: bool,
+    zero_padded: bool,
+}
+
+/// What decryption does with a coordinate pair outside the grid (e.g. "63").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidPolicy {
+    /// Drop the pair from the output.
+    #[default]
+    Skip,
+    /// Copy the raw digits into the output.
+    Passthrough,
+    /// Substitute a placeholder character.
+    Replace(char),
 }
 
+/// The standard 25-letter alphabet with I and J merged.
+const STANDARD_ALPHABET: &str = "ABCDEFGHIKLMNOPQRSTUVWXYZ";
+
+/// The classical 24-letter Greek alphabet.
+const GREEK_ALPHABET: &str = "ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ";
+
+/// Largest grid whose coordinates are still single digits.
+const MAX_SIZE: usize = 9;
+
 impl Default for Polybius {
     fn default() -> Self {
         Self::new()
@@ -29,29 +65,67 @@ impl Default for Polybius {
 impl Polybius {
     /// Creates a new Polybius square with the standard alphabet.
     pub fn new() -> Self {
-        Self::with_alphabet("ABCDEFGHIKLMNOPQRSTUVWXYZ")
+        Self::with_alphabet(STANDARD_ALPHABET)
     }
 
-    /// Creates a Polybius square with a custom 25-character alphabet.
+    /// Creates a Polybius square with a custom alphabet.
     ///
-    /// The alphabet must contain exactly 25 unique uppercase characters.
-    /// If invalid, falls back to the standard alphabet.
+    /// The alphabet holds up to 81 unique uppercase characters and fills
+    /// the smallest square that fits, row by row: 25 gives the classic 5x5
+    /// grid and 36 a 6x6 grid. Any other length leaves the end of the last
+    /// row empty, as the 24 Greek letters do in a 5x5 grid. If the alphabet
+    /// is empty or too long, falls back to the standard alphabet.
     pub fn with_alphabet(alphabet: &str) -> Self {
         let chars: Vec<char> = alphabet.chars().collect();
-        let grid = if chars.len() == 25 {
-            let mut grid = [[' '; 5]; 5];
-            for (i, &c) in chars.iter().enumerate() {
-                grid[i / 5][i % 5] = c;
+        match (2..=MAX_SIZE).find(|n| n * n >= chars.len()) {
+            Some(size) if !chars.is_empty() => {
+                Self::from_grid(size, chars.chunks(size).map(<[char]>::to_vec).collect())
             }
-            grid
-        } else {
-            Self::standard_grid()
-        };
+            _ => Self::from_grid(5, Self::standard_grid()),
+        }
+    }
 
+    fn from_grid(size: usize, grid: Vec<Vec<char>>) -> Self {
         Self {
+            size,
             grid,
             separator: String::new(),
+            invalid_policy: InvalidPolicy::Skip,
+            radix: None,
+            preserve_words: false,
+            merge: ('J', 'I'),
+            column_major: false,
+            checksum: false,
+            zero_padded: false,
+        }
+    }
+
+    /// Creates the historical Greek square of 24 letters (Α-Ω).
+    ///
+    /// The letters fill the 5x5 grid row by row, leaving the last cell
+    /// ("55") empty. Lowercase letters, including final sigma, are encoded
+    /// as their capitals; accented letters pass through unchanged.
+    pub fn greek() -> Self {
+        Self::with_alphabet(GREEK_ALPHABET)
+    }
+
+    /// Creates a 5x5 square of A-Z without `drop`, which is encoded as
+    /// `replace` instead.
+    ///
+    /// `Polybius::drop_letter('Q', 'K')` gives the common "no Q" square, in
+    /// which "QUEEN" encrypts exactly like "KUEEN". The dropped letter cannot
+    /// be recovered on decrypt. This is the general form of the standard
+    /// square, which drops J in favour of I. Returns `None` unless both are
+    /// distinct letters A-Z.
+    pub fn drop_letter(drop: char, replace: char) -> Option<Self> {
+        let (drop, replace) = (drop.to_ascii_uppercase(), replace.to_ascii_uppercase());
+        if !drop.is_ascii_uppercase() || !replace.is_ascii_uppercase() || drop == replace {
+            return None;
         }
+        let alphabet: String = ('A'..='Z').filter(|&c| c != drop).collect();
+        let mut cipher = Self::with_alphabet(&alphabet);
+        cipher.merge = (drop, replace);
+        Some(cipher)
     }
 
     /// Creates a Polybius square with a keyed alphabet.
@@ -76,26 +150,114 @@ impl Polybius {
         Self::with_alphabet(&alphabet)
     }
 
+    /// Lays the alphabet into the grid column by column instead of row by
+    /// row, as some keyed-square constructions do.
+    ///
+    /// Applies to whatever alphabet the square was built from, e.g.
+    /// `Polybius::with_key("ZEBRA").column_major()`.
+    pub fn column_major(mut self) -> Self {
+        if self.column_major {
+            return self;
+        }
+        let letters: Vec<char> = self.alphabet().chars().collect();
+        let size = self.size;
+        self.grid = (0..size)
+            .map(|row| {
+                (0..size)
+                    .map_while(|col| letters.get(col * size + row).copied())
+                    .collect()
+            })
+            .collect();
+        self.column_major = true;
+        self
+    }
+
     /// Sets the separator between coordinate pairs in the output.
     pub fn with_separator(mut self, sep: &str) -> Self {
         self.separator = sep.to_string();
         self
     }
 
-    fn standard_grid() -> [[char; 5]; 5] {
-        [
-            ['A', 'B', 'C', 'D', 'E'],
-            ['F', 'G', 'H', 'I', 'K'],
-            ['L', 'M', 'N', 'O', 'P'],
-            ['Q', 'R', 'S', 'T', 'U'],
-            ['V', 'W', 'X', 'Y', 'Z'],
-        ]
+    /// Sets how decryption handles coordinates that fall outside the grid.
+    pub fn on_invalid(mut self, policy: InvalidPolicy) -> Self {
+        self.invalid_policy = policy;
+        self
     }
 
-    fn find_position(&self, c: char) -> Option<(usize, usize)> {
-        let upper = c.to_ascii_uppercase();
-        let target = if upper == 'J' { 'I' } else { upper };
+    /// Keeps whitespace between words as its own token, separated from the
+    /// coordinates around it like any other token.
+    ///
+    /// Without this, a space separator makes "HI THERE" indistinguishable
+    /// from "HITHERE" on decrypt. With it, "HI THERE" encrypts to
+    /// "23 24   44 23 15 42 15" and decrypts back with the space: decryption
+    /// consumes exactly one separator after each token and keeps the rest.
+    pub fn preserve_words(mut self) -> Self {
+        self.preserve_words = true;
+        self
+    }
+
+    /// Appends a check digit to the ciphertext: the sum of all coordinate
+    /// digits mod 10, after one more separator.
+    ///
+    /// [`Polybius::decrypt_checked`] verifies it and returns
+    /// [`DecryptError::ChecksumMismatch`] if any digit was garbled in
+    /// transit; plain `decrypt` just drops it.
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = true;
+        self
+    }
+
+    /// Writes each row and column as two digits, so "H" becomes "0203".
+    ///
+    /// Gives fixed-width fields for tools that expect them; decryption
+    /// reads four digits per letter. Has no effect with
+    /// [`Polybius::with_combined_radix`], whose values are already padded.
+    pub fn zero_padded(mut self) -> Self {
+        self.zero_padded = true;
+        self
+    }
+
+    /// Encodes each cell as one combined value, `row * size + col` (counting
+    /// from 0), written in base `radix` instead of as two digits.
+    ///
+    /// Values are zero-padded to the width of the largest cell, so a 5x5
+    /// grid in base 36 uses a single character per letter. Digits above 9
+    /// are written as uppercase letters; the radix is clamped to 2..=36.
+    pub fn with_combined_radix(mut self, radix: u32) -> Self {
+        self.radix = Some(radix.clamp(2, 36));
+        self
+    }
+
+    /// Encodes each letter as its two-digit coordinate read as a number,
+    /// `row * 10 + col` (e.g. 23 for "H"), skipping anything not in the grid.
+    ///
+    /// This is the numeric form the Nihilist cipher adds its key to. It
+    /// ignores the separator and radix settings.
+    pub fn encode_numeric(&self, text: &str) -> Vec<u32> {
+        text.chars()
+            .filter_map(|c| self.find_position(c))
+            .map(|(row, col)| (row as u32 + 1) * 10 + col as u32 + 1)
+            .collect()
+    }
+
+    /// Returns the grid's alphabet, read row by row.
+    ///
+    /// Passing it to [`Polybius::with_alphabet`] rebuilds the same square.
+    pub fn alphabet(&self) -> String {
+        self.grid.iter().flatten().collect()
+    }
+
+    /// Returns the number of rows (and columns) in the grid.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn standard_grid() -> Vec<Vec<char>> {
+        let chars: Vec<char> = STANDARD_ALPHABET.chars().collect();
+        chars.chunks(5).map(<[char]>::to_vec).collect()
+    }
 
+    fn locate(&self, target: char) -> Option<(usize, usize)> {
         for (row, grid_row) in self.grid.iter().enumerate() {
             for (col, &cell) in grid_row.iter().enumerate() {
                 if cell == target {
@@ -106,103 +268,339 @@ impl Polybius {
         None
     }
 
+    /// Finds a character in the grid, folding the merged letter (J, unless
+    /// built with [`Polybius::drop_letter`]) into its replacement.
+    fn find_position(&self, c: char) -> Option<(usize, usize)> {
+        let mut uppercase = c.to_uppercase();
+        let upper = match (uppercase.next(), uppercase.next()) {
+            (Some(single), None) => single,
+            _ => c,
+        };
+        self.locate(upper).or_else(|| {
+            (upper == self.merge.0)
+                .then(|| self.locate(self.merge.1))
+                .flatten()
+        })
+    }
+
     fn encode_char(&self, c: char) -> Option<String> {
-        self.find_position(c)
-            .map(|(row, col)| format!("{}{}", row + 1, col + 1))
+        let (row, col) = self.find_position(c)?;
+        match self.radix {
+            Some(radix) => Some(self.encode_combined((row * self.size + col) as u32, radix)),
+            None => Some(numeric::format_padded_coord_pair(
+                ((row + 1) as u32, (col + 1) as u32),
+                "",
+                self.coordinate_width() / 2,
+            )),
+        }
     }
-}
 
-impl Cipher for Polybius {
-    fn encrypt(&self, input: &str) -> String {
-        let encoded: Vec<String> = input
-            .chars()
-            .filter_map(|c| {
-                if c.is_ascii_alphabetic() {
-                    self.encode_char(c)
-                } else {
-                    Some(c.to_string())
-                }
-            })
-            .collect();
+    /// Number of digits used to write one letter.
+    fn coordinate_width(&self) -> usize {
+        match self.radix {
+            Some(radix) => self.combined_width(radix),
+            None if self.zero_padded => 4,
+            None => 2,
+        }
+    }
+
+    /// Number of base-`radix` digits needed for the largest cell value.
+    fn combined_width(&self, radix: u32) -> usize {
+        let cells = (self.size * self.size) as u32;
+        let mut width = 1;
+        let mut capacity = radix;
+        while capacity < cells {
+            capacity *= radix;
+            width += 1;
+        }
+        width
+    }
 
-        if self.separator.is_empty() {
-            encoded.join("")
+    fn encode_combined(&self, value: u32, radix: u32) -> String {
+        let mut digits = vec!['0'; self.combined_width(radix)];
+        let mut rest = value;
+        for digit in digits.iter_mut().rev() {
+            *digit = char::from_digit(rest % radix, radix)
+                .unwrap_or('0')
+                .to_ascii_uppercase();
+            rest /= radix;
+        }
+        digits.into_iter().collect()
+    }
+
+    /// Reads one encoded cell from the start of `chars`, returning its
+    /// 1-indexed row and column and the number of characters consumed.
+    fn read_coordinate(&self, chars: &[char]) -> Option<(u32, u32, usize)> {
+        let Some(radix) = self.radix else {
+            let width = self.coordinate_width();
+            let (row, col) = numeric::read_digit_pair(chars, width / 2)?;
+            return Some((row, col, width));
+        };
+
+        let width = self.combined_width(radix);
+        let value = chars
+            .get(..width)?
+            .iter()
+            .try_fold(0u32, |value, c| Some(value * radix + c.to_digit(radix)?))?;
+        let size = self.size as u32;
+        Some((value / size + 1, value % size + 1, width))
+    }
+
+    /// Looks up a 1-indexed coordinate, returning `None` if it is off the grid.
+    fn cell(&self, row: u32, col: u32) -> Option<char> {
+        let row = (row as usize).checked_sub(1)?;
+        let col = (col as usize).checked_sub(1)?;
+        self.grid.get(row)?.get(col).copied()
+    }
+
+    /// Decrypts like [`Cipher::decrypt`], but fails on malformed input
+    /// instead of silently dropping it.
+    ///
+    /// Returns [`DecryptError::OddDigitRun`] for a coordinate digit with no
+    /// partner (e.g. the trailing "1" in "23151") and
+    /// [`DecryptError::OutOfRange`] for a coordinate outside the grid,
+    /// regardless of the [`InvalidPolicy`]. With [`Polybius::with_checksum`],
+    /// a missing or wrong check digit gives [`DecryptError::ChecksumMismatch`].
+    pub fn decrypt_checked(&self, input: &str) -> Result<String, DecryptError> {
+        if !self.checksum {
+            return self.decode(input, true).map(|(text, _)| text);
+        }
+        let (body, check) = self.split_checksum(input);
+        if check != Some(self.check_digit(body)) {
+            return Err(DecryptError::ChecksumMismatch);
+        }
+        self.decode(body, true).map(|(text, _)| text)
+    }
+
+    /// Decrypts like [`Cipher::decrypt`] and also counts the coordinate
+    /// pairs that fell outside the grid.
+    ///
+    /// Those pairs are still handled by the [`InvalidPolicy`] (skipped by
+    /// default), so the count is a quality signal for lenient decoding.
+    pub fn decrypt_report(&self, input: &str) -> (String, usize) {
+        let input = if self.checksum {
+            self.split_checksum(input).0
+        } else {
+            input
+        };
+        // Lenient decoding skips or replaces bad coordinates instead of failing.
+        self.decode(input, false).unwrap_or_default()
+    }
+
+    /// Sum of the coordinate digits in `text`, mod 10, as a decimal digit.
+    fn check_digit(&self, text: &str) -> char {
+        let radix = self.radix.unwrap_or(10);
+        let sum: u32 = text.chars().filter_map(|c| c.to_digit(radix)).sum();
+        char::from_digit(sum % 10, 10).unwrap_or('0')
+    }
+
+    /// Decrypts noisy ciphertext, dropping stray digits to stay aligned.
+    ///
+    /// Within each run of coordinate digits, spurious digits are skipped so
+    /// that as many coordinates as possible land on the grid, instead of one
+    /// extra digit shifting every pair after it. A stray digit can only be
+    /// spotted when it produces an off-grid coordinate, such as a '7' in a
+    /// 5x5 square. The remaining digits decode as with [`Cipher::decrypt`].
+    pub fn decrypt_best_effort(&self, input: &str) -> String {
+        let input = if self.checksum {
+            self.split_checksum(input).0
         } else {
-            let mut result = String::new();
-            for (i, s) in encoded.iter().enumerate() {
-                if i > 0 && s.len() == 2 && encoded.get(i - 1).is_some_and(|p| p.len() == 2) {
-                    result.push_str(&self.separator);
+            input
+        };
+        let chars: Vec<char> = input.chars().collect();
+        let mut cleaned = String::with_capacity(input.len());
+        for run in
+            chars.chunk_by(|a, b| self.is_coordinate_digit(*a) == self.is_coordinate_digit(*b))
+        {
+            if self.is_coordinate_digit(run[0]) {
+                cleaned.extend(self.resync(run));
+            } else {
+                cleaned.extend(run);
+            }
+        }
+        self.decode(&cleaned, false).unwrap_or_default().0
+    }
+
+    /// Keeps the digits of `run` that decode to the most on-grid coordinates.
+    ///
+    /// A coordinate may be read from consecutive digits or skip the one
+    /// digit before its last, so an insertion inside a pair costs nothing.
+    fn resync(&self, run: &[char]) -> Vec<char> {
+        let width = self.coordinate_width();
+        let n = run.len();
+        // best[i]: coordinates recoverable from run[i..]; take[i]: the end of
+        // the coordinate starting at i, if one is read there.
+        let mut best = vec![0; n + 1];
+        let mut take = vec![None; n + 1];
+        for i in (0..n).rev() {
+            best[i] = best[i + 1];
+            for end in [i + width, i + width + 1] {
+                if end <= n && self.on_grid(&run[i..i + width - 1], run[end - 1]) {
+                    let score = 1 + best[end];
+                    if score > best[i] || (score == best[i] && take[i].is_none()) {
+                        best[i] = score;
+                        take[i] = Some(end);
+                    }
+                }
+            }
+        }
+
+        let mut kept = Vec::with_capacity(n);
+        let mut i = 0;
+        while i < n {
+            match take[i] {
+                Some(end) => {
+                    kept.extend(&run[i..i + width - 1]);
+                    kept.push(run[end - 1]);
+                    i = end;
                 }
-                result.push_str(s);
+                None => i += 1,
             }
-            result
         }
+        kept
     }
 
-    fn decrypt(&self, input: &str) -> String {
-        let cleaned: String = input
-            .chars()
-            .filter(|c| c.is_ascii_digit() || !c.is_ascii_alphanumeric() && *c != ' ')
-            .collect();
+    fn on_grid(&self, head: &[char], last: char) -> bool {
+        let mut digits = head.to_vec();
+        digits.push(last);
+        self.read_coordinate(&digits)
+            .and_then(|(row, col, _)| self.cell(row, col))
+            .is_some()
+    }
 
-        let digits: Vec<u8> = cleaned
-            .chars()
-            .filter_map(|c| c.to_digit(10).map(|d| d as u8))
-            .collect();
+    /// Splits off the trailing check digit and the separator before it.
+    fn split_checksum<'a>(&self, input: &'a str) -> (&'a str, Option<char>) {
+        match input.char_indices().next_back() {
+            Some((i, c)) if c.is_ascii_digit() => {
+                let body = &input[..i];
+                (
+                    body.strip_suffix(self.separator.as_str()).unwrap_or(body),
+                    Some(c),
+                )
+            }
+            _ => (input, None),
+        }
+    }
 
+    /// Decodes `input`, returning the text and the number of coordinates
+    /// outside the grid.
+    fn decode(&self, input: &str, strict: bool) -> Result<(String, usize), DecryptError> {
+        let chars: Vec<char> = input.chars().collect();
+        let separator: Vec<char> = self.separator.chars().collect();
         let mut result = String::new();
-        let mut input_chars = input.chars().peekable();
-        let mut digit_idx = 0;
+        let mut i = 0;
+        let mut after_token = false;
+        let mut unmapped = 0;
 
-        while input_chars.peek().is_some() {
-            let c = input_chars.next().unwrap();
+        while i < chars.len() {
+            let at_separator = !separator.is_empty() && chars[i..].starts_with(&separator);
+            if at_separator && (after_token || !self.preserve_words) {
+                i += separator.len();
+                after_token = false;
+            } else if let Some((row, col, len)) = self.read_coordinate(&chars[i..]) {
+                if self.cell(row, col).is_none() {
+                    if strict {
+                        return Err(DecryptError::OutOfRange(i));
+                    }
+                    unmapped += 1;
+                }
+                self.push_coordinate(&mut result, row, col, &chars[i..i + len]);
+                i += len;
+                after_token = true;
+            } else if strict && self.is_coordinate_digit(chars[i]) {
+                return Err(DecryptError::OddDigitRun(i));
+            } else {
+                result.push(chars[i]);
+                after_token = self.preserve_words && chars[i].is_whitespace();
+                i += 1;
+            }
+        }
 
-            if c.is_ascii_digit() {
-                if digit_idx + 1 < digits.len() {
-                    let row = (digits[digit_idx] - 1) as usize;
-                    let col = (digits[digit_idx + 1] - 1) as usize;
+        Ok((result, unmapped))
+    }
 
-                    if row < 5 && col < 5 {
-                        result.push(self.grid[row][col]);
-                    }
-                    digit_idx += 2;
+    fn is_coordinate_digit(&self, c: char) -> bool {
+        c.is_digit(self.radix.unwrap_or(10))
+    }
 
-                    if let Some(&next) = input_chars.peek() {
-                        if next.is_ascii_digit() {
-                            continue;
-                        }
-                    }
-                }
-            } else if !self.separator.is_empty() && self.separator.starts_with(c) {
-                let sep_len = self.separator.len();
-                let mut matched = String::from(c);
-                while matched.len() < sep_len {
-                    if let Some(&next) = input_chars.peek() {
-                        if self.separator[matched.len()..].starts_with(next) {
-                            matched.push(input_chars.next().unwrap());
-                        } else {
-                            break;
-                        }
-                    } else {
-                        break;
+    fn push_coordinate(&self, result: &mut String, row: u32, col: u32, raw: &[char]) {
+        match (self.cell(row, col), self.invalid_policy) {
+            (Some(c), _) => result.push(c),
+            (None, InvalidPolicy::Skip) => {}
+            (None, InvalidPolicy::Passthrough) => result.extend(raw),
+            (None, InvalidPolicy::Replace(placeholder)) => result.push(placeholder),
+        }
+    }
+}
+
+impl Cipher for Polybius {
+    fn encrypt(&self, input: &str) -> String {
+        let mut result = String::new();
+        let mut prev_encoded = false;
+
+        for c in input.chars() {
+            let token = self
+                .encode_char(c)
+                .or_else(|| (self.preserve_words && c.is_whitespace()).then(|| c.to_string()));
+            match token {
+                Some(encoded) => {
+                    if prev_encoded {
+                        result.push_str(&self.separator);
                     }
+                    result.push_str(&encoded);
+                    prev_encoded = true;
                 }
-                if matched != self.separator {
-                    result.push_str(&matched);
+                None => {
+                    result.push(c);
+                    prev_encoded = false;
                 }
-            } else {
-                result.push(c);
             }
         }
 
+        if self.checksum {
+            let check = self.check_digit(&result);
+            result.push_str(&self.separator);
+            result.push(check);
+        }
         result
     }
+
+    fn decrypt(&self, input: &str) -> String {
+        self.decrypt_report(input).0
+    }
+
+    /// One coordinate token plus a separator per letter.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        let width = self.coordinate_width();
+        input
+            .chars()
+            .map(|c| match self.find_position(c) {
+                Some(_) => width + self.separator.len(),
+                None => c.len_utf8(),
+            })
+            .sum()
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        self.grid.iter().flatten().copied().collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn estimated_output_len_is_about_twice_the_letters() {
+        let cipher = Polybius::new();
+        assert_eq!(cipher.estimated_output_len("HELLO"), 10);
+
+        let spaced = Polybius::new().with_separator(" ");
+        let estimate = spaced.estimated_output_len("HELLO WORLD");
+        assert!(estimate >= spaced.encrypt("HELLO WORLD").len());
+        assert!(estimate <= 3 * 10 + 1);
+    }
+
     #[test]
     fn encrypt_basic() {
         let cipher = Polybius::new();
@@ -284,4 +682,345 @@ mod tests {
         let decrypted = cipher.decrypt(&encrypted);
         assert_eq!(decrypted, plaintext);
     }
+
+    #[test]
+    fn decrypt_preserves_punctuation_order() {
+        let cipher = Polybius::new();
+        assert_eq!(cipher.decrypt("11,12.13"), "A,B.C");
+    }
+
+    #[test]
+    fn invalid_policy_skip() {
+        let cipher = Polybius::new();
+        assert_eq!(cipher.decrypt("1163"), "A");
+        let cipher = Polybius::new().on_invalid(InvalidPolicy::Skip);
+        assert_eq!(cipher.decrypt("1163"), "A");
+    }
+
+    #[test]
+    fn invalid_policy_passthrough() {
+        let cipher = Polybius::new().on_invalid(InvalidPolicy::Passthrough);
+        assert_eq!(cipher.decrypt("1163"), "A63");
+    }
+
+    #[test]
+    fn invalid_policy_replace() {
+        let cipher = Polybius::new().on_invalid(InvalidPolicy::Replace('?'));
+        assert_eq!(cipher.decrypt("1163"), "A?");
+        assert_eq!(cipher.decrypt("0111"), "?A");
+    }
+
+    #[test]
+    fn standard_size() {
+        assert_eq!(Polybius::new().size(), 5);
+        assert_eq!(Polybius::with_key("KEYWORD").size(), 5);
+    }
+
+    #[test]
+    fn six_by_six_grid() {
+        let cipher = Polybius::with_alphabet("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789");
+        assert_eq!(cipher.size(), 6);
+        assert_eq!(cipher.encrypt("J"), "24");
+        assert_eq!(cipher.encrypt("I"), "23");
+        assert_eq!(cipher.encrypt("Z9"), "5266");
+
+        let plaintext = "MEET AT 1600";
+        let encrypted = cipher.encrypt(plaintext);
+        assert_eq!(cipher.decrypt(&encrypted), plaintext);
+    }
+
+    #[test]
+    fn invalid_alphabet_falls_back_to_standard() {
+        for alphabet in [String::new(), "A".repeat(82)] {
+            let cipher = Polybius::with_alphabet(&alphabet);
+            assert_eq!(cipher.size(), 5);
+            assert_eq!(cipher.encrypt("HELLO"), "2315313134");
+        }
+    }
+
+    #[test]
+    fn partial_last_row() {
+        let cipher = Polybius::with_alphabet("ABC");
+        assert_eq!(cipher.size(), 2);
+        assert_eq!(cipher.encrypt("CAB"), "211112");
+        assert_eq!(cipher.decrypt("211112"), "CAB");
+    }
+
+    #[test]
+    fn greek_alphabet_rebuilds_square() {
+        let greek = Polybius::greek();
+        let rebuilt = Polybius::with_alphabet(&greek.alphabet());
+        assert_eq!(rebuilt.alphabet(), greek.alphabet());
+        assert_eq!(rebuilt.size(), 5);
+        assert_eq!(rebuilt.encrypt("ΑΘΗΝΑ"), greek.encrypt("ΑΘΗΝΑ"));
+    }
+
+    #[test]
+    fn decrypt_partial_unsupported() {
+        let cipher = Polybius::new();
+        assert!(!cipher.preserves_length());
+        assert_eq!(cipher.decrypt_partial("2315", &[0]), "2315");
+    }
+
+    #[test]
+    fn keyed_alphabet_export() {
+        let keyed = Polybius::with_key("KEYWORD");
+        assert_eq!(keyed.alphabet(), "KEYWORDABCFGHILMNPQSTUVXZ");
+
+        let rebuilt = Polybius::with_alphabet(&keyed.alphabet());
+        assert_eq!(
+            rebuilt.encrypt("ATTACK AT DAWN"),
+            keyed.encrypt("ATTACK AT DAWN")
+        );
+    }
+
+    #[test]
+    fn standard_alphabet_export() {
+        assert_eq!(Polybius::new().alphabet(), "ABCDEFGHIKLMNOPQRSTUVWXYZ");
+    }
+
+    #[test]
+    fn greek_round_trip() {
+        let cipher = Polybius::greek();
+        let encrypted = cipher.encrypt("ΛΟΓΟΣ");
+        assert_eq!(encrypted, "3135133543");
+        assert_eq!(cipher.decrypt(&encrypted), "ΛΟΓΟΣ");
+    }
+
+    #[test]
+    fn greek_folds_lowercase() {
+        let cipher = Polybius::greek();
+        assert_eq!(cipher.encrypt("λογος"), cipher.encrypt("ΛΟΓΟΣ"));
+        assert_eq!(cipher.encrypt("ω"), "54");
+        assert_eq!(cipher.encrypt("A"), "A");
+    }
+
+    #[test]
+    fn greek_empty_cell_is_invalid() {
+        let cipher = Polybius::greek().on_invalid(InvalidPolicy::Replace('?'));
+        assert_eq!(cipher.decrypt("1155"), "Α?");
+        assert_eq!(cipher.alphabet().chars().count(), 24);
+    }
+
+    #[test]
+    fn combined_radix_round_trip() {
+        let cipher = Polybius::new().with_combined_radix(36);
+        let encrypted = cipher.encrypt("HELLO");
+        assert_eq!(encrypted, "74AAD");
+        assert_eq!(cipher.decrypt(&encrypted), "HELLO");
+        assert_eq!(cipher.decrypt("74aad"), "HELLO");
+    }
+
+    #[test]
+    fn combined_radix_pads_to_fixed_width() {
+        let cipher = Polybius::new().with_combined_radix(2).with_separator(" ");
+        assert_eq!(cipher.encrypt("AZ"), "00000 11000");
+        assert_eq!(cipher.decrypt("00000 11000"), "AZ");
+
+        let hex =
+            Polybius::with_alphabet("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789").with_combined_radix(16);
+        assert_eq!(hex.encrypt("A9"), "0023");
+        assert_eq!(hex.decrypt("0023"), "A9");
+    }
+
+    #[test]
+    fn combined_radix_out_of_range() {
+        let cipher = Polybius::new()
+            .with_combined_radix(10)
+            .on_invalid(InvalidPolicy::Passthrough);
+        assert_eq!(cipher.decrypt("0099"), "A99");
+    }
+
+    #[test]
+    fn preserve_words_round_trip() {
+        let cipher = Polybius::new().with_separator(" ").preserve_words();
+        let encrypted = cipher.encrypt("HI THERE");
+        assert_eq!(encrypted, "23 24   44 23 15 42 15");
+        assert_eq!(cipher.decrypt(&encrypted), "HI THERE");
+
+        let plain = Polybius::new().with_separator(" ");
+        assert_eq!(plain.decrypt(&plain.encrypt("HI THERE")), "HITHERE");
+    }
+
+    #[test]
+    fn preserve_words_multi_word_phrase() {
+        let phrase = "MEET ME AT THE OLD OAK, AT TEN";
+        for separator in [" ", "-", ""] {
+            let cipher = Polybius::new().with_separator(separator).preserve_words();
+            let encrypted = cipher.encrypt(phrase);
+            assert_eq!(
+                cipher.decrypt(&encrypted),
+                phrase,
+                "separator {:?}",
+                separator
+            );
+        }
+    }
+
+    #[test]
+    fn drop_letter_q() {
+        let cipher = Polybius::drop_letter('Q', 'K').unwrap();
+        assert_eq!(cipher.alphabet(), "ABCDEFGHIJKLMNOPRSTUVWXYZ");
+        assert_eq!(cipher.encrypt("QUEEN"), cipher.encrypt("KUEEN"));
+        assert_eq!(cipher.decrypt(&cipher.encrypt("QUEEN")), "KUEEN");
+        assert_ne!(cipher.encrypt("I"), cipher.encrypt("J"));
+    }
+
+    #[test]
+    fn drop_letter_rejects_invalid() {
+        assert!(Polybius::drop_letter('Q', 'Q').is_none());
+        assert!(Polybius::drop_letter('1', 'K').is_none());
+        assert!(Polybius::drop_letter('q', 'k').is_some());
+    }
+
+    #[test]
+    fn column_major_fill() {
+        let cipher = Polybius::new().column_major();
+        assert_eq!(cipher.encrypt("ABF"), "112112");
+        assert_eq!(cipher.alphabet(), "AFLQVBGMRWCHNSXDIOTYEKPUZ");
+        assert_eq!(
+            cipher.column_major().alphabet(),
+            "AFLQVBGMRWCHNSXDIOTYEKPUZ"
+        );
+    }
+
+    #[test]
+    fn column_major_keyed_round_trip() {
+        let rows = Polybius::with_key("PLAYFAIR");
+        let columns = Polybius::with_key("PLAYFAIR").column_major();
+        let plaintext = "HIDETHEGOLDINTHETREESTUMP";
+
+        let by_rows = rows.encrypt(plaintext);
+        let by_columns = columns.encrypt(plaintext);
+        assert_ne!(by_rows, by_columns);
+        assert_eq!(rows.decrypt(&by_rows), plaintext);
+        assert_eq!(columns.decrypt(&by_columns), plaintext);
+    }
+
+    #[test]
+    fn column_major_greek() {
+        let cipher = Polybius::greek().column_major();
+        let encrypted = cipher.encrypt("ΛΟΓΟΣ");
+        assert_eq!(cipher.decrypt(&encrypted), "ΛΟΓΟΣ");
+        assert_eq!(cipher.encrypt("Ω"), "45");
+    }
+
+    #[test]
+    fn decrypt_checked_accepts_valid_input() {
+        let cipher = Polybius::new().with_separator(" ");
+        assert_eq!(
+            cipher.decrypt_checked("23 15 31 31 34"),
+            Ok("HELLO".to_string())
+        );
+        assert_eq!(
+            Polybius::new().decrypt_checked("11, 12!"),
+            Ok("A, B!".to_string())
+        );
+    }
+
+    #[test]
+    fn decrypt_report_counts_unmapped_pairs() {
+        let cipher = Polybius::new();
+        assert_eq!(cipher.decrypt_report("23156631"), ("HEL".to_string(), 1));
+        assert_eq!(cipher.decrypt_report("2315"), ("HE".to_string(), 0));
+
+        let passthrough = Polybius::new().on_invalid(InvalidPolicy::Passthrough);
+        assert_eq!(passthrough.decrypt_report("2366"), ("H66".to_string(), 1));
+    }
+
+    #[test]
+    fn decrypt_best_effort_resyncs_after_stray_digit() {
+        let cipher = Polybius::new();
+        assert_eq!(cipher.encrypt("HELLOWORLD"), "23153131345234423114");
+
+        // A '7' inside the third pair and a '0' between two pairs.
+        let noisy = "2315371313452340423114";
+        assert_eq!(cipher.decrypt_best_effort(noisy), "HELLOWORLD");
+        assert_ne!(cipher.decrypt(noisy), "HELLOWORLD");
+
+        assert_eq!(cipher.decrypt_best_effort("23, 1571!"), "H, E!");
+        assert_eq!(cipher.decrypt_best_effort("2315313"), "HEL");
+        assert_eq!(cipher.decrypt_best_effort(""), "");
+    }
+
+    #[test]
+    fn zero_padded_round_trip() {
+        let cipher = Polybius::new().zero_padded();
+        assert_eq!(cipher.encrypt("HI"), "02030204");
+        assert_eq!(cipher.decrypt("02030204"), "HI");
+        assert_eq!(
+            cipher.decrypt_checked("0203020"),
+            Err(DecryptError::OddDigitRun(4))
+        );
+        assert_eq!(cipher.estimated_output_len("HI"), 8);
+
+        let combined = Polybius::new().with_combined_radix(36).zero_padded();
+        assert_eq!(
+            combined.encrypt("HI"),
+            Polybius::new().with_combined_radix(36).encrypt("HI")
+        );
+    }
+
+    #[test]
+    fn checksum_round_trip() {
+        // 2+3+1+5+3+1+3+1+3+4 = 26
+        let cipher = Polybius::new().with_separator(" ").with_checksum();
+        assert_eq!(cipher.encrypt("HELLO"), "23 15 31 31 34 6");
+        assert_eq!(
+            cipher.decrypt_checked("23 15 31 31 34 6"),
+            Ok("HELLO".to_string())
+        );
+        assert_eq!(cipher.decrypt("23 15 31 31 34 6"), "HELLO");
+
+        let packed = Polybius::new().with_checksum();
+        assert_eq!(packed.encrypt("HELLO"), "23153131346");
+        assert_eq!(
+            packed.decrypt_checked("23153131346"),
+            Ok("HELLO".to_string())
+        );
+    }
+
+    #[test]
+    fn checksum_detects_flipped_digit() {
+        let cipher = Polybius::new().with_separator(" ").with_checksum();
+        assert_eq!(
+            cipher.decrypt_checked("23 15 32 31 34 6"),
+            Err(DecryptError::ChecksumMismatch)
+        );
+        assert_eq!(
+            cipher.decrypt_checked("23 15 31 31 34"),
+            Err(DecryptError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn decrypt_checked_odd_digit_run() {
+        let cipher = Polybius::new();
+        assert_eq!(
+            cipher.decrypt_checked("23151"),
+            Err(DecryptError::OddDigitRun(4))
+        );
+        assert_eq!(cipher.decrypt("23151"), "HE1");
+    }
+
+    #[test]
+    fn decrypt_checked_out_of_range() {
+        let cipher = Polybius::new().on_invalid(InvalidPolicy::Passthrough);
+        assert_eq!(
+            cipher.decrypt_checked("1163"),
+            Err(DecryptError::OutOfRange(2))
+        );
+        assert_eq!(cipher.decrypt("1163"), "A63");
+    }
+
+    #[test]
+    fn encode_numeric() {
+        let cipher = Polybius::new();
+        assert_eq!(cipher.encode_numeric("H"), vec![23]);
+        assert_eq!(cipher.encode_numeric("A"), vec![11]);
+        assert_eq!(
+            cipher.encode_numeric("Hello, J!"),
+            vec![23, 15, 31, 31, 34, 24]
+        );
+    }
 }
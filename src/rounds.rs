@@ -0,0 +1,109 @@
+use crate::{Cipher, InputClasses};
+
+/// Applies an inner cipher repeatedly.
+///
+/// Encryption applies the inner cipher's `encrypt` `n` times; decryption
+/// applies its `decrypt` `n` times. For most classical ciphers extra rounds
+/// with the same key add no security: two rounds of Caesar(3) are just
+/// Caesar(6).
+#[derive(Debug, Clone)]
+pub struct Rounds<C> {
+    inner: C,
+    rounds: usize,
+}
+
+impl<C: Cipher> Rounds<C> {
+    /// Wraps `inner` so that it is applied `rounds` times.
+    pub fn new(inner: C, rounds: usize) -> Self {
+        Self { inner, rounds }
+    }
+
+    /// Returns the number of rounds applied.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+}
+
+impl<C: Cipher> Cipher for Rounds<C> {
+    fn encrypt(&self, input: &str) -> String {
+        (0..self.rounds).fold(input.to_string(), |text, _| self.inner.encrypt(&text))
+    }
+
+    fn decrypt(&self, input: &str) -> String {
+        (0..self.rounds).fold(input.to_string(), |text, _| self.inner.decrypt(&text))
+    }
+
+    fn preserves_length(&self) -> bool {
+        self.inner.preserves_length()
+    }
+
+    fn keyspace(&self) -> Option<u128> {
+        self.inner.keyspace()
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        self.inner.input_classes()
+    }
+
+    /// Applies the inner estimate once per round, assuming every round
+    /// grows the text by the same factor as the first.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        if self.rounds == 0 {
+            return input.len();
+        }
+        let first = self.inner.estimated_output_len(input);
+        if input.is_empty() {
+            return first;
+        }
+        (1..self.rounds).fold(first, |len, _| {
+            len.saturating_mul(first).div_ceil(input.len())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Atbash, Caesar, Polybius, Vigenere};
+
+    #[test]
+    fn caesar_rounds_compose() {
+        let rounds = Rounds::new(Caesar::new(3), 2);
+        assert_eq!(rounds.encrypt("A"), Caesar::new(6).encrypt("A"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let rounds = Rounds::new(Vigenere::new("KEY"), 3);
+        let plaintext = "The Quick Brown Fox";
+        let encrypted = rounds.encrypt(plaintext);
+        assert_eq!(rounds.decrypt(&encrypted), plaintext);
+    }
+
+    #[test]
+    fn estimated_output_len_per_round() {
+        let rounds = Rounds::new(Polybius::new(), 2);
+        assert!(rounds.estimated_output_len("HELLO") >= rounds.encrypt("HELLO").len());
+        assert_eq!(rounds.estimated_output_len("HELLO"), 20);
+        assert_eq!(
+            Rounds::new(Caesar::new(3), 3).estimated_output_len("HELLO"),
+            5
+        );
+        assert_eq!(
+            Rounds::new(Polybius::new(), 0).estimated_output_len("HELLO"),
+            5
+        );
+    }
+
+    #[test]
+    fn zero_rounds_passthrough() {
+        let rounds = Rounds::new(Caesar::new(3), 0);
+        assert_eq!(rounds.encrypt("HELLO"), "HELLO");
+    }
+
+    #[test]
+    fn even_atbash_rounds_cancel() {
+        let rounds = Rounds::new(Atbash::new(), 2);
+        assert_eq!(rounds.encrypt("HELLO"), "HELLO");
+    }
+}
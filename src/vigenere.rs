@@ -1,48 +1,398 @@
-use crate::Cipher;
+use crate::alphabet::AlphabetRef;
+use crate::analysis::{self, NgramModel};
+use crate::error::VigenereError;
+use crate::{Alphabet, Cipher, InputClasses};
 
 /// Vigenère cipher - polyalphabetic substitution using a keyword
 #[derive(Debug, Clone)]
 pub struct Vigenere {
-    key: Vec<u8>,
+    key: Vec<usize>,
+    alphabet: AlphabetRef,
+    reset_per_word: bool,
+    advance_on_all: bool,
+    ignored: Vec<char>,
+    tableau: Option<Box<Tableau>>,
 }
 
+/// A 26x26 substitution square: row = key letter, column = plaintext letter.
+type Tableau = [[char; 26]; 26];
+
 impl Vigenere {
     pub fn new(key: &str) -> Self {
-        let key: Vec<u8> = key
-            .to_uppercase()
+        Self::with_alphabet(key, Alphabet::latin())
+    }
+
+    /// Like [`Vigenere::new`], but rejects keys that are empty or contain
+    /// anything other than letters instead of silently dropping characters.
+    pub fn try_new(key: &str) -> Result<Self, VigenereError> {
+        let alphabet = Alphabet::latin();
+        if let Some(c) = key.chars().find(|&c| alphabet.index_of(c).is_none()) {
+            return Err(VigenereError::NonAlphabetic(c));
+        }
+        if key.is_empty() {
+            return Err(VigenereError::Empty);
+        }
+        Ok(Self::with_alphabet(key, alphabet))
+    }
+
+    /// Creates a Vigenère cipher over the 36 symbols A-Z then 0-9.
+    ///
+    /// Letters and digits in both the key and the text are shifted mod 36,
+    /// so "ABC123" is fully encrypted. Because the alphabet differs, a
+    /// letters-only message does not encrypt the same as with [`Vigenere::new`].
+    /// Lowercase letters keep their case unless they encrypt to a digit, in
+    /// which case they decrypt back as uppercase.
+    pub fn alphanumeric(key: &str) -> Self {
+        Self::with_alphabet(key, Alphabet::latin_alphanumeric())
+    }
+
+    /// Creates a Vigenère cipher over the 52 symbols A-Z then a-z.
+    ///
+    /// Key and text letters are shifted mod 52, so case is part of the
+    /// encryption rather than preserved: "H" under key "a" becomes "h".
+    pub fn mixed_case(key: &str) -> Self {
+        Self::with_alphabet(key, Alphabet::latin_mixed_case())
+    }
+
+    /// Shifts only vowels, wrapping within A, E, I, O, U; consonants pass
+    /// through without using up a key letter.
+    ///
+    /// Key letters are read as A-Z shifts (A=0, B=1, ...) and taken mod 5,
+    /// so every letter of the key counts: "BC" shifts the vowels by 1 then
+    /// 2. Note that [`Vigenere::with_alphabet`] with [`Alphabet::vowels`]
+    /// instead drops key letters that are not vowels.
+    pub fn vowels_only(key: &str) -> Self {
+        Self::over_subset(key, Alphabet::vowels())
+    }
+
+    /// Shifts only consonants, wrapping within the 21 of them; vowels pass
+    /// through. Key letters are read as A-Z shifts taken mod 21, as in
+    /// [`Vigenere::vowels_only`].
+    pub fn consonants_only(key: &str) -> Self {
+        Self::over_subset(key, Alphabet::consonants())
+    }
+
+    fn over_subset(key: &str, alphabet: Alphabet) -> Self {
+        let latin = Alphabet::latin();
+        let len = alphabet.len();
+        let mut cipher = Self::with_alphabet("", alphabet);
+        cipher.key = key
             .chars()
-            .filter(|c| c.is_ascii_alphabetic())
-            .map(|c| (c as u8) - b'A')
+            .filter_map(|c| latin.index_of(c))
+            .map(|i| i % len)
+            .collect();
+        cipher
+    }
+
+    /// Creates a Vigenère cipher that shifts within `alphabet`.
+    ///
+    /// Key characters outside the alphabet are ignored, so over a subset
+    /// such as [`Alphabet::vowels`] most of a key may vanish; see
+    /// [`Vigenere::vowels_only`] for a subset cipher that keeps the key.
+    pub fn with_alphabet(key: &str, alphabet: Alphabet) -> Self {
+        Self {
+            key: key.chars().filter_map(|c| alphabet.index_of(c)).collect(),
+            alphabet: alphabet.into(),
+            reset_per_word: false,
+            advance_on_all: false,
+            ignored: Vec::new(),
+            tableau: None,
+        }
+    }
+
+    /// Creates a Vigenère cipher over a custom tableau instead of shifts.
+    ///
+    /// Encryption replaces plaintext letter `p` under key letter `k` with
+    /// `tableau[k][p]`; decryption finds the ciphertext letter in row `k`.
+    /// Each row may be any arrangement of A-Z, as in some historical field
+    /// ciphers. Returns `None` if a row is not a permutation of A-Z.
+    pub fn with_tableau(key: &str, tableau: Tableau) -> Option<Self> {
+        let is_permutation = |row: &[char; 26]| {
+            let mut sorted = *row;
+            sorted.sort_unstable();
+            sorted.iter().copied().eq('A'..='Z')
+        };
+        if !tableau.iter().all(is_permutation) {
+            return None;
+        }
+        let mut cipher = Self::new(key);
+        cipher.tableau = Some(Box::new(tableau));
+        Some(cipher)
+    }
+
+    /// Restarts the key at its first letter after every run of non-letters.
+    ///
+    /// By default the keystream runs continuously across word boundaries.
+    pub fn reset_per_word(mut self) -> Self {
+        self.reset_per_word = true;
+        self
+    }
+
+    /// Advances the key on every character, not just letters.
+    ///
+    /// Spaces and punctuation stay unshifted but still use up a key letter,
+    /// matching tools that index the key by character position. If
+    /// [`Vigenere::reset_per_word`] is also set, the reset wins.
+    pub fn advance_on_all(mut self) -> Self {
+        self.advance_on_all = true;
+        self
+    }
+
+    /// Passes `chars` through untouched without moving the keystream, even
+    /// if they are letters.
+    ///
+    /// Takes precedence over [`Vigenere::reset_per_word`] and
+    /// [`Vigenere::advance_on_all`], so hyphens in "CO-OP" can be skipped
+    /// while other punctuation still advances the key.
+    pub fn ignoring(mut self, chars: &[char]) -> Self {
+        self.ignored = chars.to_vec();
+        self
+    }
+
+    /// Summarizes the key for logging, e.g.
+    /// "Vigenère, key length 3, period 3, key shifts [10, 4, 24]".
+    ///
+    /// The period is the length of the shortest repeating unit of the key,
+    /// so "KEYKEY" has length 6 but period 3.
+    pub fn describe(&self) -> String {
+        let len = self.key.len();
+        let period = analysis::repeat_period(&self.key);
+        let mut description = format!(
+            "Vigenère, key length {}, period {}, key shifts {:?}",
+            len, period, self.key
+        );
+        if self.tableau.is_some() {
+            description.push_str(", custom tableau");
+        }
+        description
+    }
+
+    /// Returns the key repeated (or cut short) to `plaintext_len_letters`
+    /// symbols, as written above the plaintext when working by hand.
+    ///
+    /// An empty key gives an empty string.
+    pub fn key_worm(&self, plaintext_len_letters: usize) -> String {
+        self.key
+            .iter()
+            .cycle()
+            .take(plaintext_len_letters)
+            .filter_map(|&k| self.alphabet.char_at(k))
+            .collect()
+    }
+
+    /// Starts an encrypting stream that keeps its key position across chunks.
+    pub fn stream(&self) -> VigenereStream<'_> {
+        VigenereStream::new(self, false)
+    }
+
+    /// Starts a decrypting stream that keeps its key position across chunks.
+    pub fn decrypt_stream(&self) -> VigenereStream<'_> {
+        VigenereStream::new(self, true)
+    }
+
+    /// Recovers a key of length `known.len()` when some key letters are known.
+    ///
+    /// `known` holds the key letter (either case) at each fixed position and
+    /// `None` where it is unknown; bytes other than A-Z count as unknown. Only the unknown positions are searched:
+    /// each starts at the shift whose column best matches English letter
+    /// frequencies, and is then refined by hill climbing on `model` until no
+    /// single change improves the score. Returns the full key in uppercase.
+    pub fn solve_partial(ciphertext: &str, known: &[Option<u8>], model: &NgramModel) -> String {
+        let period = known.len();
+        let columns = analysis::columns(ciphertext, period);
+        let known: Vec<Option<u8>> = known
+            .iter()
+            .map(|k| {
+                k.filter(u8::is_ascii_alphabetic)
+                    .map(|letter| letter.to_ascii_uppercase() - b'A')
+            })
             .collect();
-        Self { key }
+        let mut key: Vec<u8> = known
+            .iter()
+            .zip(&columns)
+            .map(|(k, column)| k.unwrap_or_else(|| best_column_shift(column)))
+            .collect();
+
+        let unknown: Vec<usize> = (0..period).filter(|&i| known[i].is_none()).collect();
+        let score = |key: &[u8]| model.score(&Self::new(&key_string(key)).decrypt(ciphertext));
+        let mut best = score(&key);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for &i in &unknown {
+                let current = key[i];
+                for shift in 0..26 {
+                    key[i] = shift;
+                    let candidate = score(&key);
+                    if candidate > best {
+                        best = candidate;
+                        improved = true;
+                    } else {
+                        key[i] = current;
+                    }
+                }
+            }
+        }
+        key_string(&key)
+    }
+
+    /// Breaks a Vigenère ciphertext with no known key letters.
+    ///
+    /// Tries a single shift and the key lengths whose columns have the most
+    /// English-like index of coincidence, solves each with
+    /// [`solve_partial`](Self::solve_partial), and keeps the one whose
+    /// plaintext `model` scores best. A key that repeats itself is cut to
+    /// its shortest period. Returns `(key, plaintext)`.
+    pub fn crack(ciphertext: &str, model: &NgramModel) -> (String, String) {
+        std::iter::once(1)
+            .chain(analysis::likely_periods(ciphertext))
+            .map(|period| {
+                let mut key = Self::solve_partial(ciphertext, &vec![None; period], model);
+                key.truncate(analysis::repeat_period(key.as_bytes()));
+                let plaintext = Self::new(&key).decrypt(ciphertext);
+                (key, plaintext)
+            })
+            .max_by(|a, b| {
+                model
+                    .score(&a.1)
+                    .total_cmp(&model.score(&b.1))
+                    .then(b.0.len().cmp(&a.0.len()))
+            })
+            .unwrap_or_default()
     }
 
     fn transform(&self, input: &str, decrypt: bool) -> String {
+        self.transform_from(input, &mut 0, decrypt)
+    }
+
+    fn transform_from(&self, input: &str, key_index: &mut usize, decrypt: bool) -> String {
         if self.key.is_empty() {
             return input.to_string();
         }
 
-        let mut key_index = 0;
-        input
-            .chars()
-            .map(|c| {
-                if c.is_ascii_alphabetic() {
-                    let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
-                    let offset = (c as u8 - base) as i32;
-                    let key_shift = self.key[key_index % self.key.len()] as i32;
-                    key_index += 1;
-
-                    let shifted = if decrypt {
-                        (offset - key_shift).rem_euclid(26)
-                    } else {
-                        (offset + key_shift).rem_euclid(26)
-                    };
-                    (base + shifted as u8) as char
-                } else {
-                    c
+        let mut output = String::with_capacity(input.len());
+        for c in input.chars() {
+            output.push(self.transform_char(c, key_index, decrypt));
+        }
+        output
+    }
+
+    fn transform_char(&self, c: char, key_index: &mut usize, decrypt: bool) -> char {
+        if self.ignored.contains(&c) {
+            return c;
+        }
+        let key_shift = self.key[*key_index % self.key.len()] as i64;
+        let shift = if decrypt { -key_shift } else { key_shift };
+        let substituted = match &self.tableau {
+            Some(tableau) => look_up(&tableau[key_shift as usize], c, decrypt),
+            None => self.alphabet.shift(c, shift),
+        };
+        match substituted {
+            Some(shifted) => {
+                *key_index += 1;
+                shifted
+            }
+            None => {
+                if self.reset_per_word {
+                    *key_index = 0;
+                } else if self.advance_on_all {
+                    *key_index += 1;
                 }
-            })
+                c
+            }
+        }
+    }
+}
+
+/// Picks the Caesar shift that makes one column of letters look most like
+/// English by letter frequency.
+fn best_column_shift(column: &str) -> u8 {
+    let decrypted = |shift: u8| -> String {
+        column
+            .bytes()
+            .map(|b| b.to_ascii_uppercase())
+            .map(|b| (b'A' + (b - b'A' + 26 - shift) % 26) as char)
             .collect()
+    };
+    (0..26)
+        .max_by(|&a, &b| {
+            analysis::english_score(&decrypted(a))
+                .total_cmp(&analysis::english_score(&decrypted(b)))
+        })
+        .unwrap_or(0)
+}
+
+/// Spells key shifts as uppercase letters.
+fn key_string(key: &[u8]) -> String {
+    key.iter().map(|&k| (b'A' + k) as char).collect()
+}
+
+/// Encrypts or decrypts one letter through a tableau row, keeping its case.
+fn look_up(row: &[char; 26], c: char, decrypt: bool) -> Option<char> {
+    let upper = c.to_ascii_uppercase();
+    let result = if decrypt {
+        (b'A' + row.iter().position(|&r| r == upper)? as u8) as char
+    } else if upper.is_ascii_uppercase() {
+        row[(upper as u8 - b'A') as usize]
+    } else {
+        return None;
+    };
+    Some(if c.is_ascii_lowercase() {
+        result.to_ascii_lowercase()
+    } else {
+        result
+    })
+}
+
+/// Wipes the key, leaving a cipher that passes text through unchanged.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Vigenere {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+        if let Some(tableau) = self.tableau.as_deref_mut() {
+            tableau.zeroize();
+        }
+        self.tableau = None;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Vigenere {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+/// Incremental Vigenère transform for chunked input.
+///
+/// Each call to [`push`](VigenereStream::push) continues the keystream where
+/// the previous chunk left off, so feeding "HEL" then "LO" gives the same
+/// result as encrypting "HELLO" in one go.
+#[derive(Debug, Clone)]
+pub struct VigenereStream<'a> {
+    cipher: &'a Vigenere,
+    key_index: usize,
+    decrypt: bool,
+}
+
+impl<'a> VigenereStream<'a> {
+    fn new(cipher: &'a Vigenere, decrypt: bool) -> Self {
+        Self {
+            cipher,
+            key_index: 0,
+            decrypt,
+        }
+    }
+
+    /// Transforms the next chunk of input.
+    pub fn push(&mut self, chunk: &str) -> String {
+        self.cipher
+            .transform_from(chunk, &mut self.key_index, self.decrypt)
+    }
+
+    /// Rewinds the keystream to the first key letter.
+    pub fn reset(&mut self) {
+        self.key_index = 0;
     }
 }
 
@@ -54,12 +404,134 @@ impl Cipher for Vigenere {
     fn decrypt(&self, input: &str) -> String {
         self.transform(input, true)
     }
+
+    fn preserves_length(&self) -> bool {
+        true
+    }
+
+    /// Keys of this length over the alphabet, saturating at `u128::MAX`.
+    fn keyspace(&self) -> Option<u128> {
+        let len = u32::try_from(self.key.len()).unwrap_or(u32::MAX);
+        Some((self.alphabet.len() as u128).saturating_pow(len))
+    }
+
+    fn input_classes(&self) -> InputClasses {
+        self.alphabet.classes()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn solve_partial_fills_unknown_positions() {
+        let plaintext = "IT WAS THE BEST OF TIMES IT WAS THE WORST OF TIMES IT WAS THE AGE OF \
+                         WISDOM IT WAS THE AGE OF FOOLISHNESS IT WAS THE EPOCH OF BELIEF";
+        let ciphertext = Vigenere::new("LAMP").encrypt(plaintext);
+        let known = [Some(b'L'), None, Some(b'm'), None];
+        let key = Vigenere::solve_partial(&ciphertext, &known, NgramModel::english());
+        assert_eq!(key, "LAMP");
+    }
+
+    #[test]
+    fn solve_partial_treats_non_letters_as_unknown() {
+        let plaintext = "IT WAS THE BEST OF TIMES IT WAS THE WORST OF TIMES IT WAS THE AGE OF \
+                         WISDOM IT WAS THE AGE OF FOOLISHNESS IT WAS THE EPOCH OF BELIEF";
+        let ciphertext = Vigenere::new("LAMP").encrypt(plaintext);
+        let known = [Some(b'L'), Some(b'?'), Some(b'M'), Some(b'7')];
+        let key = Vigenere::solve_partial(&ciphertext, &known, NgramModel::english());
+        assert_eq!(key, "LAMP");
+    }
+
+    #[test]
+    fn crack_recovers_key_and_plaintext() {
+        let plaintext = "It was the best of times, it was the worst of times, it was the age \
+                         of wisdom, it was the age of foolishness, it was the epoch of belief, \
+                         it was the epoch of incredulity, it was the season of Light, it was \
+                         the season of Darkness, it was the spring of hope, it was the winter \
+                         of despair.";
+        let ciphertext = Vigenere::new("LAMP").encrypt(plaintext);
+        let (key, recovered) = Vigenere::crack(&ciphertext, NgramModel::english());
+        assert_eq!(key, "LAMP");
+        assert_eq!(recovered, plaintext);
+    }
+
+    #[test]
+    fn mixed_case_shifts_over_52_symbols() {
+        let cipher = Vigenere::mixed_case("aB");
+        assert_eq!(cipher.encrypt("Hello"), "hfLmO");
+        assert_eq!(cipher.decrypt("hfLmO"), "Hello");
+        assert_eq!(cipher.encrypt("Hi, you!"), "hj, YpU!");
+        assert_ne!(
+            cipher.encrypt("Hello"),
+            Vigenere::new("aB").encrypt("Hello")
+        );
+    }
+
+    #[test]
+    fn vowels_only_keeps_whole_key() {
+        let cipher = Vigenere::vowels_only("BC");
+        assert_eq!(cipher.encrypt("Quiet, Bud!"), "Qauit, Bed!");
+        assert_eq!(cipher.decrypt("Qauit, Bed!"), "Quiet, Bud!");
+        assert_eq!(Vigenere::vowels_only("KEY").key_worm(3), "AUU");
+        assert_eq!(
+            Vigenere::with_alphabet("KEY", Alphabet::vowels()).key_worm(3),
+            "EEE"
+        );
+    }
+
+    #[test]
+    fn consonants_only_keeps_vowels() {
+        let cipher = Vigenere::consonants_only("BC");
+        assert_eq!(cipher.encrypt("Dizzy"), "Ficbb");
+        assert_eq!(cipher.decrypt("Ficbb"), "Dizzy");
+    }
+
+    #[test]
+    fn ignoring_skips_keystream() {
+        let cipher = Vigenere::new("KEY").advance_on_all().ignoring(&['-']);
+        let coop = cipher.encrypt("COOP");
+        assert_eq!(
+            cipher.encrypt("CO-OP"),
+            format!("{}-{}", &coop[..2], &coop[2..])
+        );
+        assert_eq!(cipher.decrypt(&cipher.encrypt("CO-OP")), "CO-OP");
+        assert_ne!(
+            Vigenere::new("KEY").advance_on_all().encrypt("CO-OP"),
+            cipher.encrypt("CO-OP")
+        );
+    }
+
+    #[test]
+    fn ignoring_letters() {
+        let cipher = Vigenere::new("B").ignoring(&['Q', 'q']);
+        assert_eq!(cipher.encrypt("Quiz"), "Qvja");
+    }
+
+    #[test]
+    fn key_worm_repeats_key() {
+        assert_eq!(Vigenere::new("KEY").key_worm(7), "KEYKEYK");
+        assert_eq!(Vigenere::new("key").key_worm(2), "KE");
+        assert_eq!(Vigenere::new("").key_worm(5), "");
+        assert_eq!(Vigenere::alphanumeric("A1").key_worm(3), "A1A");
+    }
+
+    #[test]
+    fn try_new_rejects_empty_key() {
+        assert_eq!(Vigenere::try_new("").unwrap_err(), VigenereError::Empty);
+    }
+
+    #[test]
+    fn try_new_rejects_digits() {
+        assert_eq!(
+            Vigenere::try_new("K3Y").unwrap_err(),
+            VigenereError::NonAlphabetic('3')
+        );
+        assert_eq!(Vigenere::new("K3Y").encrypt("HELLO"), "RCVJY");
+        assert_eq!(Vigenere::try_new("Key").unwrap().encrypt("HELLO"), "RIJVS");
+    }
+
     #[test]
     fn encrypt_basic() {
         let cipher = Vigenere::new("KEY");
@@ -93,4 +565,174 @@ mod tests {
         let cipher = Vigenere::new("");
         assert_eq!(cipher.encrypt("Hello"), "Hello");
     }
+
+    #[test]
+    fn reset_per_word() {
+        let continuous = Vigenere::new("KEY");
+        let reset = Vigenere::new("KEY").reset_per_word();
+
+        let encrypted = reset.encrypt("HELLO WORLD");
+        assert_eq!(encrypted, "RIJVS GSPVH");
+        assert_ne!(encrypted, continuous.encrypt("HELLO WORLD"));
+        assert_eq!(reset.decrypt(&encrypted), "HELLO WORLD");
+    }
+
+    #[test]
+    fn describe() {
+        let description = Vigenere::new("KEY").describe();
+        assert!(description.contains("length 3"), "{}", description);
+        assert!(description.contains("[10, 4, 24]"), "{}", description);
+        assert!(Vigenere::new("KEYKEY")
+            .describe()
+            .contains("length 6, period 3"));
+        assert!(Vigenere::new("").describe().contains("length 0, period 0"));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_wipes_key() {
+        use zeroize::Zeroize;
+
+        let mut cipher = Vigenere::new("SECRET");
+        assert_ne!(cipher.encrypt("HELLO"), "HELLO");
+        cipher.zeroize();
+        assert!(cipher.key.is_empty());
+        assert_eq!(cipher.encrypt("HELLO"), "HELLO");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_wipes_tableau() {
+        use zeroize::Zeroize;
+
+        let mut cipher = Vigenere::with_tableau("KEY", reversed_tableau()).unwrap();
+        cipher.zeroize();
+        assert!(cipher.tableau.is_none());
+        assert_eq!(cipher.encrypt("HELLO"), "HELLO");
+    }
+
+    #[test]
+    fn keyspace() {
+        assert_eq!(Vigenere::new("KEY").keyspace(), Some(26 * 26 * 26));
+        assert_eq!(Vigenere::alphanumeric("K3").keyspace(), Some(36 * 36));
+    }
+
+    #[test]
+    fn advance_on_all() {
+        let letters_only = Vigenere::new("KEY");
+        let every_char = Vigenere::new("KEY").advance_on_all();
+
+        let a = letters_only.encrypt("HI THERE");
+        let b = every_char.encrypt("HI THERE");
+        assert_eq!(a, "RM RRIPO");
+        assert_eq!(b, "RM DLCBI");
+        assert_eq!(letters_only.decrypt(&a), "HI THERE");
+        assert_eq!(every_char.decrypt(&b), "HI THERE");
+    }
+
+    #[test]
+    fn alphanumeric_round_trip() {
+        let cipher = Vigenere::alphanumeric("K3Y");
+        let encrypted = cipher.encrypt("USER42");
+        assert_eq!(encrypted, "4L21XQ");
+        assert_eq!(cipher.decrypt(&encrypted), "USER42");
+    }
+
+    #[test]
+    fn alphanumeric_differs_from_mod_26() {
+        let encrypted = Vigenere::alphanumeric("KEY").encrypt("HELLO");
+        assert_eq!(encrypted, "RI9VS");
+        assert_ne!(encrypted, Vigenere::new("KEY").encrypt("HELLO"));
+    }
+
+    #[test]
+    fn alphanumeric_preserves_other_characters() {
+        let cipher = Vigenere::alphanumeric("KEY");
+        let encrypted = cipher.encrypt("user-42, ok");
+        assert!(encrypted.contains('-'));
+        assert!(encrypted.contains(", "));
+        assert_eq!(cipher.decrypt(&encrypted).to_lowercase(), "user-42, ok");
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let alphabet = Alphabet::new("АБВГДЕЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯ")
+            .unwrap()
+            .with_case_folding();
+        let cipher = Vigenere::with_alphabet("КЛЮЧ", alphabet);
+        let encrypted = cipher.encrypt("Привет, мир");
+        assert_ne!(encrypted, "Привет, мир");
+        assert_eq!(cipher.decrypt(&encrypted), "Привет, мир");
+    }
+
+    /// Rows are the reversed alphabet rotated by the key letter (Beaufort-like).
+    fn reversed_tableau() -> [[char; 26]; 26] {
+        let mut tableau = [['A'; 26]; 26];
+        for (k, row) in tableau.iter_mut().enumerate() {
+            for (p, cell) in row.iter_mut().enumerate() {
+                *cell = (b'A' + ((25 + k - p) % 26) as u8) as char;
+            }
+        }
+        tableau
+    }
+
+    #[test]
+    fn tableau_round_trip() {
+        let cipher = Vigenere::with_tableau("KEY", reversed_tableau()).unwrap();
+        let encrypted = cipher.encrypt("Attack at dawn!");
+        assert_eq!(encrypted, "Jkejbn jk ujhk!");
+        assert_eq!(cipher.decrypt(&encrypted), "Attack at dawn!");
+    }
+
+    #[test]
+    fn standard_tableau_matches_shifts() {
+        let mut tableau = [['A'; 26]; 26];
+        for (k, row) in tableau.iter_mut().enumerate() {
+            for (p, cell) in row.iter_mut().enumerate() {
+                *cell = (b'A' + ((k + p) % 26) as u8) as char;
+            }
+        }
+        let cipher = Vigenere::with_tableau("KEY", tableau).unwrap();
+        assert_eq!(
+            cipher.encrypt("HELLO"),
+            Vigenere::new("KEY").encrypt("HELLO")
+        );
+    }
+
+    #[test]
+    fn tableau_rejects_non_permutation() {
+        let mut tableau = reversed_tableau();
+        tableau[3][0] = tableau[3][1];
+        assert!(Vigenere::with_tableau("KEY", tableau).is_none());
+    }
+
+    #[test]
+    fn stream_matches_one_shot() {
+        let cipher = Vigenere::new("KEY");
+        let mut stream = cipher.stream();
+        let chunked = stream.push("HEL") + &stream.push("LO");
+        assert_eq!(chunked, cipher.encrypt("HELLO"));
+    }
+
+    #[test]
+    fn stream_reset() {
+        let cipher = Vigenere::new("KEY");
+        let mut stream = cipher.stream();
+        stream.push("HEL");
+        stream.reset();
+        assert_eq!(stream.push("HELLO"), cipher.encrypt("HELLO"));
+    }
+
+    #[test]
+    fn decrypt_stream_round_trip() {
+        let cipher = Vigenere::new("SECRET").reset_per_word();
+        let encrypted = cipher.encrypt("The Quick Brown Fox");
+        let mut stream = cipher.decrypt_stream();
+        let decrypted = encrypted
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| stream.push(std::str::from_utf8(chunk).unwrap()))
+            .collect::<String>();
+        assert_eq!(decrypted, "The Quick Brown Fox");
+    }
 }
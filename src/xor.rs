@@ -1,47 +1,344 @@
-use crate::Cipher;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::analysis;
+use crate::{Alphabet, Cipher, InputClasses};
 
 /// XOR cipher - symmetric encryption using repeating key
+///
+/// Optionally rotates each output byte left by a fixed number of bits after
+/// XORing, which makes encryption and decryption distinct operations.
 #[derive(Debug, Clone)]
 pub struct Xor {
     key: Vec<u8>,
+    rotation: u32,
+    alphabet: Option<Alphabet>,
 }
 
 impl Xor {
     pub fn new(key: &[u8]) -> Self {
-        Self { key: key.to_vec() }
+        Self::with_rotation(key, 0)
+    }
+
+    /// Reads the raw bytes of `path` as the key.
+    ///
+    /// An empty key file is reported as `InvalidData`.
+    pub fn from_key_file(path: &Path) -> io::Result<Self> {
+        let key = fs::read(path)?;
+        if key.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "empty key file"));
+        }
+        Ok(Self::new(&key))
     }
 
     pub fn with_str_key(key: &str) -> Self {
         Self::new(key.as_bytes())
     }
 
-    fn transform(&self, input: &str) -> String {
-        if self.key.is_empty() {
-            return input.to_string();
+    /// XORs with a repeating key, then rotates each byte left by `rotate` bits.
+    ///
+    /// Decryption rotates right before XORing. Rotations are taken mod 8.
+    pub fn with_rotation(key: &[u8], rotate: u32) -> Self {
+        Self {
+            key: key.to_vec(),
+            rotation: rotate % 8,
+            alphabet: None,
         }
+    }
 
-        input
-            .bytes()
-            .enumerate()
-            .map(|(i, b)| {
-                let key_byte = self.key[i % self.key.len()];
-                (b ^ key_byte) as char
-            })
-            .collect()
+    /// XORs symbol positions within `alphabet` instead of bytes, so text
+    /// stays inside the alphabet.
+    ///
+    /// Each character's index is XORed with the next key index; characters
+    /// outside the alphabet pass through without using up a key index. The
+    /// transform is its own inverse. The alphabet length must be a power of
+    /// two up to 256 so every result is a valid index; returns `None` if it
+    /// is not, if the alphabet repeats a symbol, or if a key index is out of
+    /// range. The byte methods ignore the alphabet.
+    pub fn over_alphabet(key: &[usize], alphabet: &[char]) -> Option<Self> {
+        let len = alphabet.len();
+        if !len.is_power_of_two() || len > 256 || key.iter().any(|&k| k >= len) {
+            return None;
+        }
+        let alphabet = Alphabet::new(&alphabet.iter().collect::<String>())?;
+        Some(Self {
+            key: key.iter().map(|&k| k as u8).collect(),
+            rotation: 0,
+            alphabet: Some(alphabet),
+        })
+    }
+
+    fn is_identity(&self) -> bool {
+        self.key.is_empty() && self.rotation == 0
+    }
+
+    fn key_byte(&self, index: usize) -> u8 {
+        if self.key.is_empty() {
+            0
+        } else {
+            self.key[index % self.key.len()]
+        }
     }
 
     /// XOR raw bytes, returning bytes (useful for binary data)
+    ///
+    /// This is the encryption direction; without rotation it is also its own inverse.
     pub fn transform_bytes(&self, input: &[u8]) -> Vec<u8> {
-        if self.key.is_empty() {
-            return input.to_vec();
+        self.encrypt_bytes(input)
+    }
+
+    /// Encrypts raw bytes: XOR with the key, then rotate left.
+    pub fn encrypt_bytes(&self, input: &[u8]) -> Vec<u8> {
+        self.transform_from(input, 0, false)
+    }
+
+    /// Decrypts raw bytes: rotate right, then XOR with the key.
+    pub fn decrypt_bytes(&self, input: &[u8]) -> Vec<u8> {
+        self.transform_from(input, 0, true)
+    }
+
+    /// Checks that `ciphertext` decrypts to `expected_plaintext`.
+    ///
+    /// The comparison looks at every byte instead of stopping at the first
+    /// difference, so its running time does not reveal how much of the
+    /// plaintext matched. Only the length may leak: a length mismatch
+    /// returns false straight away.
+    pub fn verify(&self, ciphertext: &[u8], expected_plaintext: &[u8]) -> bool {
+        if ciphertext.len() != expected_plaintext.len() {
+            return false;
         }
+        let difference = self
+            .decrypt_bytes(ciphertext)
+            .iter()
+            .zip(expected_plaintext)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        std::hint::black_box(difference) == 0
+    }
+
+    /// Reads `input` as raw bytes, encrypts them and writes `output`.
+    pub fn encrypt_file_bytes(&self, input: &Path, output: &Path) -> io::Result<()> {
+        fs::write(output, self.encrypt_bytes(&fs::read(input)?))
+    }
+
+    /// Reads `input` as raw bytes, decrypts them and writes `output`.
+    pub fn decrypt_file_bytes(&self, input: &Path, output: &Path) -> io::Result<()> {
+        fs::write(output, self.decrypt_bytes(&fs::read(input)?))
+    }
+
+    /// Starts an encrypting stream that keeps its key position across chunks.
+    pub fn stream(&self) -> XorStream<'_> {
+        XorStream::new(self, false)
+    }
+
+    /// Starts a decrypting stream that keeps its key position across chunks.
+    pub fn decrypt_stream(&self) -> XorStream<'_> {
+        XorStream::new(self, true)
+    }
 
+    /// Transforms `input` as if it started at byte `offset` of the message.
+    fn transform_from(&self, input: &[u8], offset: usize, decrypt: bool) -> Vec<u8> {
         input
             .iter()
             .enumerate()
             .map(|(i, &b)| {
-                let key_byte = self.key[i % self.key.len()];
-                b ^ key_byte
+                let key = self.key_byte(offset + i);
+                if decrypt {
+                    b.rotate_right(self.rotation) ^ key
+                } else {
+                    (b ^ key).rotate_left(self.rotation)
+                }
+            })
+            .collect()
+    }
+
+    /// XORs two equal-length ciphertexts together.
+    ///
+    /// If both were encrypted with the same key (and no rotation), the key
+    /// cancels out and the result is the XOR of the two plaintexts, which is
+    /// why a one-time pad must never be reused. Returns `None` if the lengths
+    /// differ.
+    pub fn xor_ciphertexts(a: &[u8], b: &[u8]) -> Option<Vec<u8>> {
+        if a.len() != b.len() {
+            return None;
+        }
+        Some(a.iter().zip(b).map(|(x, y)| x ^ y).collect())
+    }
+}
+
+impl Xor {
+    /// Ranks candidate key sizes for a repeating-key XOR ciphertext.
+    ///
+    /// For each size from 1 to `max`, consecutive key-size blocks are compared
+    /// by Hamming distance, normalized by the block size. The true key size
+    /// (and its multiples) lines the blocks up under the same key bytes and
+    /// gives the smallest distance, so the result is sorted from most to
+    /// least likely. Sizes that do not fit at least two blocks are skipped.
+    pub fn guess_key_size(ciphertext: &[u8], max: usize) -> Vec<usize> {
+        let mut scored: Vec<(usize, f64)> = (1..=max)
+            .filter_map(|size| Some((size, block_distance(ciphertext, size)?)))
+            .collect();
+        scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+        scored.into_iter().map(|(size, _)| size).collect()
+    }
+
+    /// Recovers a single-byte XOR key by trying all 256 candidates.
+    ///
+    /// Each candidate plaintext is scored with [`analysis::english_score`],
+    /// and the best key is returned along with its plaintext.
+    pub fn crack_single_byte(ciphertext: &[u8]) -> (u8, Vec<u8>) {
+        let (key, plaintext, _) = Self::best_single_byte(ciphertext);
+        (key, plaintext)
+    }
+
+    /// Like [`Xor::crack_single_byte`], but only returns a result when the
+    /// best plaintext's [`analysis::english_score`] is at least `threshold`.
+    pub fn crack_auto(ciphertext: &[u8], threshold: f64) -> Option<(u8, Vec<u8>)> {
+        let (key, plaintext, score) = Self::best_single_byte(ciphertext);
+        (score >= threshold).then_some((key, plaintext))
+    }
+
+    fn best_single_byte(ciphertext: &[u8]) -> (u8, Vec<u8>, f64) {
+        (0..=255u8)
+            .map(|key| {
+                let plaintext: Vec<u8> = ciphertext.iter().map(|b| b ^ key).collect();
+                let score = analysis::english_score(&latin1(&plaintext));
+                (key, plaintext, score)
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .expect("key range is non-empty")
+    }
+
+    /// Breaks repeating-key XOR, returning the recovered key and plaintext.
+    ///
+    /// The most likely key sizes from [`Xor::guess_key_size`] are each tried
+    /// in turn: the ciphertext is split into one column per key byte, each
+    /// column is solved with [`Xor::crack_single_byte`], and the key whose
+    /// plaintext looks most like English wins. A key that repeats itself is
+    /// shortened to its smallest period.
+    pub fn crack(ciphertext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let max_size = (ciphertext.len() / 2).clamp(1, MAX_CRACK_KEY_SIZE);
+        let mut sizes = Self::guess_key_size(ciphertext, max_size);
+        sizes.truncate(CRACK_CANDIDATES);
+        if sizes.is_empty() {
+            sizes.push(1);
+        }
+
+        sizes
+            .into_iter()
+            .map(|size| {
+                let mut key = Self::crack_columns(ciphertext, size);
+                key.truncate(analysis::repeat_period(&key));
+                let plaintext = Self::new(&key).decrypt_bytes(ciphertext);
+                let score = analysis::english_score(&latin1(&plaintext));
+                (key, plaintext, score)
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(key, plaintext, _)| (key, plaintext))
+            .expect("at least one key size is tried")
+    }
+
+    fn crack_columns(ciphertext: &[u8], size: usize) -> Vec<u8> {
+        (0..size)
+            .map(|offset| {
+                let column: Vec<u8> = ciphertext
+                    .iter()
+                    .skip(offset)
+                    .step_by(size)
+                    .copied()
+                    .collect();
+                Self::crack_single_byte(&column).0
+            })
+            .collect()
+    }
+}
+
+/// Largest key size [`Xor::crack`] considers.
+const MAX_CRACK_KEY_SIZE: usize = 40;
+
+/// Number of top-ranked key sizes [`Xor::crack`] fully solves.
+const CRACK_CANDIDATES: usize = 3;
+
+/// Views bytes as text, one char per byte, for scoring.
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+/// Average normalized Hamming distance between consecutive blocks of `size`.
+fn block_distance(data: &[u8], size: usize) -> Option<f64> {
+    let blocks: Vec<&[u8]> = data.chunks_exact(size).collect();
+    if blocks.len() < 2 {
+        return None;
+    }
+
+    let total: u32 = blocks
+        .windows(2)
+        .filter_map(|pair| analysis::hamming_distance(pair[0], pair[1]))
+        .sum();
+    Some(total as f64 / (blocks.len() - 1) as f64 / size as f64)
+}
+
+/// Wipes the key, leaving only the bit rotation (if any) in effect.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Xor {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Xor {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+/// Incremental XOR transform for chunked byte streams.
+///
+/// Each call to [`push`](XorStream::push) continues the key where the
+/// previous chunk left off, so feeding a buffer in pieces gives the same
+/// bytes as transforming it in one go.
+#[derive(Debug, Clone)]
+pub struct XorStream<'a> {
+    cipher: &'a Xor,
+    offset: usize,
+    decrypt: bool,
+}
+
+impl<'a> XorStream<'a> {
+    fn new(cipher: &'a Xor, decrypt: bool) -> Self {
+        Self {
+            cipher,
+            offset: 0,
+            decrypt,
+        }
+    }
+
+    /// Transforms the next chunk of bytes.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<u8> {
+        let output = self.cipher.transform_from(chunk, self.offset, self.decrypt);
+        self.offset += chunk.len();
+        output
+    }
+
+    /// Rewinds the stream to the first key byte.
+    pub fn reset(&mut self) {
+        self.offset = 0;
+    }
+}
+
+impl Xor {
+    fn transform_symbols(&self, alphabet: &Alphabet, input: &str) -> String {
+        let mut key_index = 0;
+        input
+            .chars()
+            .map(|c| match alphabet.index_of(c) {
+                Some(index) => {
+                    let key = self.key_byte(key_index) as usize;
+                    key_index += 1;
+                    alphabet.char_at(index ^ key).unwrap_or(c)
+                }
+                None => c,
             })
             .collect()
     }
@@ -49,17 +346,65 @@ impl Xor {
 
 impl Cipher for Xor {
     fn encrypt(&self, input: &str) -> String {
-        self.transform(input)
+        if let Some(alphabet) = &self.alphabet {
+            return self.transform_symbols(alphabet, input);
+        }
+        if self.is_identity() {
+            return input.to_string();
+        }
+        self.encrypt_bytes(input.as_bytes())
+            .into_iter()
+            .map(char::from)
+            .collect()
     }
 
+    /// Reads each char as the byte `encrypt` wrote it from, then decodes
+    /// the result as UTF-8, falling back to one char per byte if it is not.
     fn decrypt(&self, input: &str) -> String {
-        self.transform(input)
+        if let Some(alphabet) = &self.alphabet {
+            return self.transform_symbols(alphabet, input);
+        }
+        if self.is_identity() {
+            return input.to_string();
+        }
+        let bytes: Vec<u8> = input.chars().map(|c| c as u32 as u8).collect();
+        String::from_utf8(self.decrypt_bytes(&bytes))
+            .unwrap_or_else(|err| err.into_bytes().into_iter().map(char::from).collect())
+    }
+
+    /// Bytes above 0x7F come out as two-byte UTF-8 characters.
+    fn estimated_output_len(&self, input: &str) -> usize {
+        if self.alphabet.is_some() || self.is_identity() {
+            input.len()
+        } else {
+            input.len() * 2
+        }
+    }
+
+    fn preserves_length(&self) -> bool {
+        self.alphabet.is_some()
+    }
+
+    /// Keys of this length, saturating at `u128::MAX`.
+    fn keyspace(&self) -> Option<u128> {
+        let symbols = self.alphabet.as_ref().map_or(256, Alphabet::len) as u128;
+        let len = u32::try_from(self.key.len()).unwrap_or(u32::MAX);
+        Some(symbols.saturating_pow(len))
+    }
+
+    /// Every byte, or the classes of the symbols when over an alphabet.
+    fn input_classes(&self) -> InputClasses {
+        match &self.alphabet {
+            Some(alphabet) => alphabet.classes(),
+            None => InputClasses::ALL,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testutil::{random_bytes, ENGLISH};
 
     #[test]
     fn symmetric() {
@@ -70,6 +415,16 @@ mod tests {
         assert_eq!(decrypted, original);
     }
 
+    #[test]
+    fn rotation_round_trips_strings() {
+        let cipher = Xor::with_rotation(b"KEY", 3);
+        for plaintext in ["Hello, world", "héllo wörld"] {
+            let encrypted = cipher.encrypt(plaintext);
+            assert_ne!(encrypted, plaintext);
+            assert_eq!(cipher.decrypt(&encrypted), plaintext);
+        }
+    }
+
     #[test]
     fn single_byte_key() {
         let cipher = Xor::new(&[0x20]);
@@ -81,6 +436,7 @@ mod tests {
     fn empty_key_passthrough() {
         let cipher = Xor::new(&[]);
         assert_eq!(cipher.encrypt("Hello"), "Hello");
+        assert_eq!(cipher.encrypt("héllo"), "héllo");
     }
 
     #[test]
@@ -90,4 +446,230 @@ mod tests {
         let output = cipher.transform_bytes(&input);
         assert_eq!(output, vec![0xFF, 0xF0, 0x0F]);
     }
+
+    #[test]
+    fn rotation_round_trip() {
+        let cipher = Xor::with_rotation(&[0x5A, 0xC3, 0x01], 3);
+        let input: Vec<u8> = (0..=255).collect();
+        let encrypted = cipher.encrypt_bytes(&input);
+        assert_ne!(
+            encrypted,
+            Xor::new(&[0x5A, 0xC3, 0x01]).encrypt_bytes(&input)
+        );
+        assert_eq!(cipher.decrypt_bytes(&encrypted), input);
+    }
+
+    #[test]
+    fn rotation_applies_after_xor() {
+        let cipher = Xor::with_rotation(&[0x0F], 3);
+        // 0x01 ^ 0x0F = 0x0E, rotated left by 3 = 0x70
+        assert_eq!(cipher.encrypt_bytes(&[0x01]), vec![0x70]);
+        assert_eq!(cipher.decrypt_bytes(&[0x70]), vec![0x01]);
+    }
+
+    #[test]
+    fn guess_key_size_finds_key_length() {
+        let ciphertext = Xor::new(b"\x1f\xa2\x4c\x07").encrypt_bytes(ENGLISH.as_bytes());
+        let ranked = Xor::guess_key_size(&ciphertext, 12);
+        assert_eq!(ranked.len(), 12);
+        assert!(ranked[..3].iter().any(|size| size % 4 == 0), "{:?}", ranked);
+    }
+
+    #[test]
+    fn guess_key_size_skips_oversized_candidates() {
+        let ranked = Xor::guess_key_size(b"short", 4);
+        assert_eq!(ranked.len(), 2);
+        assert!(!ranked.contains(&3));
+    }
+
+    #[test]
+    fn crack_single_byte_recovers_key() {
+        let plaintext = b"Cooking MC's like a pound of bacon, and the bass is kicking";
+        let ciphertext = Xor::new(&[0x58]).encrypt_bytes(plaintext);
+        let (key, recovered) = Xor::crack_single_byte(&ciphertext);
+        assert_eq!(key, 0x58);
+        assert_eq!(recovered, plaintext);
+    }
+
+    #[test]
+    fn crack_single_byte_on_longer_text() {
+        let ciphertext = Xor::new(&[0xB7]).encrypt_bytes(ENGLISH.as_bytes());
+        let (key, recovered) = Xor::crack_single_byte(&ciphertext);
+        assert_eq!(key, 0xB7);
+        assert_eq!(recovered, ENGLISH.as_bytes());
+    }
+
+    #[test]
+    fn stream_matches_one_shot() {
+        let cipher = Xor::new(b"SECRET");
+        let mut stream = cipher.stream();
+        let mut chunked = stream.push(&ENGLISH.as_bytes()[..7]);
+        chunked.extend(stream.push(&ENGLISH.as_bytes()[7..]));
+        assert_eq!(chunked, cipher.transform_bytes(ENGLISH.as_bytes()));
+    }
+
+    #[test]
+    fn decrypt_stream_with_rotation() {
+        let cipher = Xor::with_rotation(b"KEY", 3);
+        let encrypted = cipher.encrypt_bytes(b"Hello, World!");
+        let mut stream = cipher.decrypt_stream();
+        let mut decrypted = stream.push(&encrypted[..5]);
+        decrypted.extend(stream.push(&encrypted[5..]));
+        assert_eq!(decrypted, b"Hello, World!");
+
+        stream.reset();
+        assert_eq!(stream.push(&encrypted[..5]), b"Hello");
+    }
+
+    const SYMBOLS: [char; 32] = [
+        'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R',
+        'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '2', '3', '4', '5', '6', '7',
+    ];
+
+    #[test]
+    fn over_alphabet_round_trip() {
+        let cipher = Xor::over_alphabet(&[7, 19, 30, 2], &SYMBOLS).unwrap();
+        let plaintext = "ATTACK AT 2300, DAWN";
+        let encrypted = cipher.encrypt(plaintext);
+        assert_ne!(encrypted, plaintext);
+        assert_eq!(encrypted.len(), plaintext.len());
+        assert!(encrypted
+            .chars()
+            .all(|c| SYMBOLS.contains(&c) || " 0,".contains(c)));
+        assert_eq!(cipher.decrypt(&encrypted), plaintext);
+        assert_eq!(cipher.encrypt(&encrypted), plaintext);
+    }
+
+    #[test]
+    fn over_alphabet_xors_indices() {
+        // B (1) ^ 3 = 2 -> C; C (2) ^ 3 = 1 -> B.
+        let cipher = Xor::over_alphabet(&[3], &SYMBOLS).unwrap();
+        assert_eq!(cipher.encrypt("BC!"), "CB!");
+        assert_eq!(cipher.keyspace(), Some(32));
+    }
+
+    #[test]
+    fn over_alphabet_rejects_invalid() {
+        let letters: Vec<char> = ('A'..='Z').collect();
+        assert!(Xor::over_alphabet(&[1], &letters).is_none());
+        assert!(Xor::over_alphabet(&[32], &SYMBOLS).is_none());
+        assert!(Xor::over_alphabet(&[1], &['A', 'A']).is_none());
+        assert!(Xor::over_alphabet(&[1], &[]).is_none());
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_wipes_key() {
+        use zeroize::Zeroize;
+
+        let mut cipher = Xor::with_str_key("KEY");
+        assert_ne!(cipher.encrypt_bytes(b"data"), b"data");
+        cipher.zeroize();
+        assert!(cipher.key.is_empty());
+        assert_eq!(cipher.encrypt_bytes(b"data"), b"data");
+    }
+
+    #[test]
+    fn verify_compares_plaintexts() {
+        let cipher = Xor::new(b"KEY");
+        let ciphertext = cipher.encrypt_bytes(b"open sesame");
+        assert!(cipher.verify(&ciphertext, b"open sesame"));
+        assert!(!cipher.verify(&ciphertext, b"open sesamE"));
+        assert!(!cipher.verify(&ciphertext, b"Open sesame"));
+    }
+
+    #[test]
+    fn verify_rejects_length_mismatch() {
+        let cipher = Xor::new(b"KEY");
+        let ciphertext = cipher.encrypt_bytes(b"open sesame");
+        assert!(!cipher.verify(&ciphertext, b"open"));
+        assert!(!cipher.verify(&ciphertext, b"open sesame!"));
+        assert!(cipher.verify(&[], &[]));
+    }
+
+    #[test]
+    fn from_key_file_matches_direct_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.bin");
+        let key = [0x00, 0x7f, 0xff, b'K'];
+        std::fs::write(&path, key).unwrap();
+
+        let cipher = Xor::from_key_file(&path).unwrap();
+        let data = b"attack at dawn";
+        assert_eq!(
+            cipher.encrypt_bytes(data),
+            Xor::new(&key).encrypt_bytes(data)
+        );
+
+        std::fs::write(&path, []).unwrap();
+        let err = Xor::from_key_file(&path).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(Xor::from_key_file(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn file_bytes_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.bin");
+        let encrypted = dir.path().join("encrypted.bin");
+        let decrypted = dir.path().join("decrypted.bin");
+        let data: Vec<u8> = (0..=255).collect();
+        std::fs::write(&plain, &data).unwrap();
+
+        let cipher = Xor::with_rotation(b"KEY", 3);
+        cipher.encrypt_file_bytes(&plain, &encrypted).unwrap();
+        assert_eq!(
+            std::fs::read(&encrypted).unwrap(),
+            cipher.encrypt_bytes(&data)
+        );
+        cipher.decrypt_file_bytes(&encrypted, &decrypted).unwrap();
+        assert_eq!(std::fs::read(&decrypted).unwrap(), data);
+    }
+
+    #[test]
+    fn keyspace_grows_with_key_length() {
+        assert_eq!(Xor::new(b"K").keyspace(), Some(256));
+        assert_eq!(Xor::new(b"KEY").keyspace(), Some(1 << 24));
+        assert_eq!(Xor::new(&[0; 20]).keyspace(), Some(u128::MAX));
+    }
+
+    #[test]
+    fn xor_ciphertexts_cancels_reused_key() {
+        let cipher = Xor::new(b"\x8e\x17\x4d\xc2\x09\x5a\xf1\x33\x60\xbb\x2e\x94");
+        let a = b"ATTACK AT DAWN";
+        let b = b"RETREAT AT TEN";
+        let combined =
+            Xor::xor_ciphertexts(&cipher.encrypt_bytes(a), &cipher.encrypt_bytes(b)).unwrap();
+        let expected: Vec<u8> = a.iter().zip(b).map(|(x, y)| x ^ y).collect();
+        assert_eq!(combined, expected);
+    }
+
+    #[test]
+    fn xor_ciphertexts_rejects_length_mismatch() {
+        assert_eq!(Xor::xor_ciphertexts(b"abc", b"ab"), None);
+        assert_eq!(Xor::xor_ciphertexts(b"", b""), Some(Vec::new()));
+    }
+
+    #[test]
+    fn crack_auto_accepts_english() {
+        let ciphertext = Xor::new(&[0x2A]).encrypt_bytes(ENGLISH.as_bytes());
+        let (key, recovered) = Xor::crack_auto(&ciphertext, 0.75).unwrap();
+        assert_eq!(key, 0x2A);
+        assert_eq!(recovered, ENGLISH.as_bytes());
+    }
+
+    #[test]
+    fn crack_auto_rejects_noise() {
+        let noise = random_bytes(0x1234_5678, 400);
+        assert_eq!(Xor::crack_auto(&noise, 0.75), None);
+    }
+
+    #[test]
+    fn crack_recovers_multi_byte_key() {
+        let key = b"Lydia";
+        let ciphertext = Xor::new(key).encrypt_bytes(ENGLISH.as_bytes());
+        let (recovered_key, plaintext) = Xor::crack(&ciphertext);
+        assert_eq!(recovered_key, key);
+        assert_eq!(plaintext, ENGLISH.as_bytes());
+    }
 }